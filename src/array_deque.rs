@@ -45,6 +45,12 @@ impl<T> ArrayDeque<T> {
     }
 }
 
+impl<T> Default for ArrayDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> List<T> for ArrayDeque<T> {
     fn size(&self) -> usize {
         self.size
@@ -104,7 +110,7 @@ impl<T> List<T> for ArrayDeque<T> {
                     self.shift_right(0, index);
                     self.increment_index();
                 } else {
-                    self.shift_left(index, self.size());
+                    self.shift_left(index, self.size() - 1);
                 }
 
                 self.size -= 1;
@@ -118,6 +124,16 @@ impl<T> List<T> for ArrayDeque<T> {
             _ => unreachable!(),
         }
     }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), CollectionError> {
+        if a >= self.size() || b >= self.size() {
+            return Err(OUT_OF_BOUND_ERROR);
+        }
+
+        self.backend
+            .swap(self.backend_index(a), self.backend_index(b));
+        Ok(())
+    }
 }
 
 impl<T> Deque<T> for ArrayDeque<T> {
@@ -140,6 +156,10 @@ impl<T> Deque<T> for ArrayDeque<T> {
     }
 
     fn remove_last(&mut self) -> Option<T> {
+        if self.size() == 0 {
+            return None;
+        }
+
         self.remove(self.size() - 1)
     }
 }
@@ -151,6 +171,7 @@ impl<T> ArrayDeque<T> {
 
     fn is_size_down_required(&self) -> bool {
         self.size() * SIZE_DOWN_THRESHOLD < self.backend_len()
+            && self.backend_len() > DEFAULT_BACKEND_SIZE
     }
 
     fn size_up(&mut self) {
@@ -171,14 +192,13 @@ impl<T> ArrayDeque<T> {
         self.index = 0;
     }
 
+    // the backend wraps the indices itself, wrapping them here would break ranges that wrap
     fn shift_right(&mut self, from: usize, to: usize) {
-        self.backend
-            .shift_right(self.backend_index(from), self.backend_index(to));
+        self.backend.shift_right(self.index + from, self.index + to);
     }
 
     fn shift_left(&mut self, from: usize, to: usize) {
-        self.backend
-            .shift_left(self.backend_index(from), self.backend_index(to));
+        self.backend.shift_left(self.index + from, self.index + to);
     }
 
     fn shift_left_underflow(&mut self, index: usize) {
@@ -230,4 +250,38 @@ mod tests {
 
         assert_eq!(deque.size(), 0);
     }
+
+    #[test]
+    pub fn drain_and_refill() {
+        let mut deque = ArrayDeque::new();
+        assert_eq!(deque.remove_first(), None);
+        assert_eq!(deque.remove_last(), None);
+
+        for _ in 0..3 {
+            deque.add_first(1);
+            deque.add_last(2);
+            assert_eq!(deque.remove_last(), Some(2));
+            assert_eq!(deque.remove_first(), Some(1));
+            assert_eq!(deque.remove_last(), None);
+        }
+    }
+
+    #[test]
+    pub fn remove_wrapped() {
+        let mut deque = ArrayDeque::new();
+        for item in 1..5 {
+            deque.add_last(item);
+        }
+        // the front moves to the end of the backend
+        assert_eq!(deque.remove_first(), Some(1));
+        deque.add_last(5);
+        deque.add_first(0);
+
+        assert_eq!(deque.remove(3), Some(4));
+        assert_eq!(deque.remove(1), Some(2));
+        assert_eq!(deque.get(0), Some(&0));
+        assert_eq!(deque.get(1), Some(&3));
+        assert_eq!(deque.get(2), Some(&5));
+        assert_eq!(deque.size(), 3);
+    }
 }
@@ -37,6 +37,12 @@ impl<T> ArrayQueue<T> {
     }
 }
 
+impl<T> Default for ArrayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> List<T> for ArrayQueue<T> {
     fn size(&self) -> usize {
         self.size
@@ -99,6 +105,16 @@ impl<T> List<T> for ArrayQueue<T> {
             _ => unreachable!(),
         }
     }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), CollectionError> {
+        if a >= self.size() || b >= self.size() {
+            return Err(OUT_OF_BOUND_ERROR);
+        }
+
+        self.backend
+            .swap(self.backend_index(a), self.backend_index(b));
+        Ok(())
+    }
 }
 
 impl<T> Queue<T> for ArrayQueue<T> {
@@ -121,6 +137,7 @@ impl<T> ArrayQueue<T> {
 
     fn is_size_down_required(&self) -> bool {
         self.size() * SIZE_DOWN_THRESHOLD < self.backend_len()
+            && self.backend_len() > DEFAULT_BACKEND_SIZE
     }
 
     fn size_up(&mut self) {
@@ -141,9 +158,9 @@ impl<T> ArrayQueue<T> {
         self.index = 0;
     }
 
+    // the backend wraps the indices itself, wrapping them here would break ranges that wrap
     fn shift_right(&mut self, from: usize, to: usize) {
-        self.backend
-            .shift_right(self.backend_index(from), self.backend_index(to));
+        self.backend.shift_right(self.index + from, self.index + to);
     }
 }
 
@@ -191,4 +208,30 @@ mod tests {
 
         assert_eq!(queue.size(), 0);
     }
+
+    #[test]
+    pub fn drain_and_refill() {
+        let mut queue = ArrayQueue::new();
+        assert_eq!(queue.dequeue(), None);
+
+        for _ in 0..3 {
+            queue.enqueue(1);
+            queue.enqueue(2);
+            assert_eq!(queue.dequeue(), Some(1));
+            assert_eq!(queue.dequeue(), Some(2));
+            assert_eq!(queue.dequeue(), None);
+        }
+
+        // the items wrap around the end of the backend
+        for item in 1..5 {
+            queue.enqueue(item);
+        }
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(5);
+
+        assert_eq!(queue.remove(3), Some(5));
+        assert_eq!(queue.get(0), Some(&2));
+        assert_eq!(queue.get(1), Some(&3));
+        assert_eq!(queue.get(2), Some(&4));
+    }
 }
@@ -22,11 +22,87 @@ impl<T> ArrayStack<T> {
         }
     }
 
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            stack: self,
+            index: 0,
+        }
+    }
+
     fn backend_len(&self) -> usize {
         self.backend.len()
     }
 }
 
+/// Iterates the items from the bottom of the stack.
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    stack: &'a ArrayStack<T>,
+    index: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let item = self.stack.get(self.index)?;
+        self.index += 1;
+        Some(item)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Moves the items out from the bottom of the stack.
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    stack: ArrayStack<T>,
+    index: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index >= self.stack.size {
+            return None;
+        }
+
+        // takes the items out of the backend without shifting the rest
+        let item = match self.stack.backend.remove(self.index) {
+            Some(Entry::Item(item)) => item,
+            _ => unreachable!(),
+        };
+        self.index += 1;
+        Some(item)
+    }
+}
+
+impl<T> IntoIterator for ArrayStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            stack: self,
+            index: 0,
+        }
+    }
+}
+
+impl<T> Default for ArrayStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> List<T> for ArrayStack<T> {
     fn size(&self) -> usize {
         self.size
@@ -88,6 +164,15 @@ impl<T> List<T> for ArrayStack<T> {
             _ => unreachable!(),
         }
     }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), CollectionError> {
+        if a >= self.size() || b >= self.size() {
+            return Err(OUT_OF_BOUND_ERROR);
+        }
+
+        self.backend.swap(a, b);
+        Ok(())
+    }
 }
 
 impl<T> Stack<T> for ArrayStack<T> {
@@ -99,6 +184,10 @@ impl<T> Stack<T> for ArrayStack<T> {
     }
 
     fn pop(&mut self) -> Option<T> {
+        if self.size() == 0 {
+            return None;
+        }
+
         self.remove(self.size() - 1)
     }
 }
@@ -110,6 +199,7 @@ impl<T> ArrayStack<T> {
 
     fn is_size_down_required(&self) -> bool {
         self.size() * SIZE_DOWN_THRESHOLD < self.backend_len()
+            && self.backend_len() > DEFAULT_BACKEND_SIZE
     }
 
     fn size_up(&mut self) {
@@ -132,8 +222,9 @@ impl<T> ArrayStack<T> {
         self.backend.shift_right(index, self.size());
     }
 
+    // moves the items after the removed `index` one slot down, before `size` is decremented
     fn shift_left_to(&mut self, index: usize) {
-        self.backend.shift_left(index, self.size());
+        self.backend.shift_left(index, self.size() - 1);
     }
 }
 
@@ -155,6 +246,10 @@ mod tests {
         stack.push(5);
 
         assert_eq!(stack.size(), 5);
+        assert_eq!(
+            stack.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
 
         assert_eq!(stack.get(0), Some(&1));
         assert_eq!(stack.get(1), Some(&2));
@@ -181,4 +276,32 @@ mod tests {
 
         assert_eq!(stack.size(), 0);
     }
+
+    #[test]
+    pub fn drain_and_refill() {
+        let mut stack = ArrayStack::new();
+        assert_eq!(stack.pop(), None);
+
+        for _ in 0..3 {
+            stack.push(1);
+            stack.push(2);
+            assert_eq!(stack.pop(), Some(2));
+            assert_eq!(stack.pop(), Some(1));
+            assert_eq!(stack.pop(), None);
+        }
+    }
+
+    #[test]
+    pub fn into_iter() {
+        let mut stack = ArrayStack::new();
+        stack.push(String::from("a"));
+        stack.push(String::from("b"));
+        stack.push(String::from("c"));
+
+        let mut items = stack.into_iter();
+        assert_eq!(items.next(), Some(String::from("a")));
+        assert_eq!(items.next(), Some(String::from("b")));
+        // the rest is dropped with the iterator
+        drop(items);
+    }
 }
@@ -0,0 +1,455 @@
+use crate::binary_search_tree::{self, SearchNode};
+use crate::{Range, SSet};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::RangeBounds;
+
+type Tree<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    item: T,
+    height: usize,
+    left: Tree<T>,
+    right: Tree<T>,
+}
+
+impl<T> Node<T> {
+    fn new(item: T) -> Box<Node<T>> {
+        Box::new(Node {
+            item,
+            height: 1,
+            left: None,
+            right: None,
+        })
+    }
+}
+
+impl<T> SearchNode for Node<T> {
+    type Item = T;
+
+    fn item(&self) -> &T {
+        &self.item
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+/// A binary search tree whose subtrees differ in height by at most one, of height under
+/// 1.44 log2(n + 2): `add`, `take`, `find`, `successor`, `predecessor` and the ends take O(log n),
+/// `range` O(log n + k) for k items.
+#[derive(Debug)]
+pub struct AvlTree<T> {
+    root: Tree<T>,
+    size: usize,
+}
+
+impl<T> AvlTree<T> {
+    pub fn new() -> AvlTree<T> {
+        AvlTree {
+            root: None,
+            size: 0,
+        }
+    }
+}
+
+impl<T: Ord> AvlTree<T> {
+    /// Checks that the items are sorted, the heights are up to date and the heights of the two
+    /// subtrees of every node differ by at most one. O(n).
+    pub fn is_valid(&self) -> bool {
+        match check(&self.root, None, None) {
+            Some((_, count)) => count == self.size,
+            None => false,
+        }
+    }
+}
+
+impl<T> Default for AvlTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> SSet<T> for AvlTree<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, item: T) -> bool {
+        let (root, added) = insert(self.root.take(), item);
+        self.root = Some(root);
+
+        if added {
+            self.size += 1;
+        }
+        added
+    }
+
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let root = self.root.take()?;
+        let (root, removed) = delete(root, item);
+        self.root = root;
+
+        if removed.is_some() {
+            self.size -= 1;
+        }
+        removed
+    }
+
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        binary_search_tree::find(self.root.as_deref(), item)
+    }
+
+    fn successor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        binary_search_tree::successor(self.root.as_deref(), item)
+    }
+
+    fn predecessor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        binary_search_tree::predecessor(self.root.as_deref(), item)
+    }
+
+    fn first(&self) -> Option<&T> {
+        binary_search_tree::first(self.root.as_deref())
+    }
+
+    fn last(&self) -> Option<&T> {
+        binary_search_tree::last(self.root.as_deref())
+    }
+
+    fn pop_first(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let (root, min) = delete_min(root);
+        self.root = root;
+        self.size -= 1;
+
+        Some(min)
+    }
+
+    fn pop_last(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let (root, max) = delete_max(root);
+        self.root = root;
+        self.size -= 1;
+
+        Some(max)
+    }
+
+    fn range<Q, R>(&self, bounds: R) -> Range<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        binary_search_tree::range(self.root.as_deref(), bounds)
+    }
+}
+
+// the height and the number of items of a valid subtree with items between `low` and `high`
+fn check<'a, T: Ord>(
+    tree: &'a Tree<T>,
+    low: Option<&'a T>,
+    high: Option<&'a T>,
+) -> Option<(usize, usize)> {
+    let node = match tree {
+        Some(node) => node,
+        None => return Some((0, 0)),
+    };
+    if matches!(low, Some(low) if *low >= node.item)
+        || matches!(high, Some(high) if *high <= node.item)
+    {
+        return None;
+    }
+
+    let (left, left_count) = check(&node.left, low, Some(&node.item))?;
+    let (right, right_count) = check(&node.right, Some(&node.item), high)?;
+    if left > right + 1 || right > left + 1 || node.height != left.max(right) + 1 {
+        return None;
+    }
+    Some((node.height, left_count + right_count + 1))
+}
+
+fn height<T>(tree: &Tree<T>) -> usize {
+    match tree {
+        Some(node) => node.height,
+        None => 0,
+    }
+}
+
+fn update_height<T>(node: &mut Node<T>) {
+    node.height = height(&node.left).max(height(&node.right)) + 1;
+}
+
+fn rotate_left<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut right = node
+        .right
+        .take()
+        .expect("rotate left requires a right child");
+    node.right = right.left.take();
+    update_height(&mut node);
+    right.left = Some(node);
+    update_height(&mut right);
+    right
+}
+
+fn rotate_right<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut left = node
+        .left
+        .take()
+        .expect("rotate right requires a left child");
+    node.left = left.right.take();
+    update_height(&mut node);
+    left.right = Some(node);
+    update_height(&mut left);
+    left
+}
+
+// restores |height(left) - height(right)| <= 1 after a single insertion or deletion below
+fn rebalance<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let left = height(&node.left);
+    let right = height(&node.right);
+
+    if left > right + 1 {
+        let child = node.left.take().expect("left child exists");
+        node.left = Some(if height(&child.left) < height(&child.right) {
+            rotate_left(child)
+        } else {
+            child
+        });
+        rotate_right(node)
+    } else if right > left + 1 {
+        let child = node.right.take().expect("right child exists");
+        node.right = Some(if height(&child.right) < height(&child.left) {
+            rotate_right(child)
+        } else {
+            child
+        });
+        rotate_left(node)
+    } else {
+        update_height(&mut node);
+        node
+    }
+}
+
+fn insert<T: Ord>(tree: Tree<T>, item: T) -> (Box<Node<T>>, bool) {
+    let mut node = match tree {
+        Some(node) => node,
+        None => return (Node::new(item), true),
+    };
+
+    let added = match item.cmp(node.item.borrow()) {
+        Ordering::Less => {
+            let (left, added) = insert(node.left.take(), item);
+            node.left = Some(left);
+            added
+        }
+        Ordering::Greater => {
+            let (right, added) = insert(node.right.take(), item);
+            node.right = Some(right);
+            added
+        }
+        Ordering::Equal => return (node, false),
+    };
+
+    (rebalance(node), added)
+}
+
+fn delete<T, Q>(mut node: Box<Node<T>>, item: &Q) -> (Tree<T>, Option<T>)
+where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    match item.cmp(node.item.borrow()) {
+        Ordering::Less => {
+            let left = match node.left.take() {
+                Some(left) => left,
+                None => return (Some(node), None),
+            };
+            let (left, removed) = delete(left, item);
+            node.left = left;
+            (Some(rebalance(node)), removed)
+        }
+        Ordering::Greater => {
+            let right = match node.right.take() {
+                Some(right) => right,
+                None => return (Some(node), None),
+            };
+            let (right, removed) = delete(right, item);
+            node.right = right;
+            (Some(rebalance(node)), removed)
+        }
+        Ordering::Equal => match (node.left.take(), node.right.take()) {
+            (None, None) => (None, Some(node.item)),
+            (Some(child), None) | (None, Some(child)) => (Some(child), Some(node.item)),
+            (Some(left), Some(right)) => {
+                let (right, min) = delete_min(right);
+                node.left = Some(left);
+                node.right = right;
+                let removed = std::mem::replace(&mut node.item, min);
+                (Some(rebalance(node)), Some(removed))
+            }
+        },
+    }
+}
+
+fn delete_min<T>(mut node: Box<Node<T>>) -> (Tree<T>, T) {
+    match node.left.take() {
+        None => (node.right.take(), node.item),
+        Some(left) => {
+            let (left, min) = delete_min(left);
+            node.left = left;
+            (Some(rebalance(node)), min)
+        }
+    }
+}
+
+fn delete_max<T>(mut node: Box<Node<T>>) -> (Tree<T>, T) {
+    match node.right.take() {
+        None => (node.left.take(), node.item),
+        Some(right) => {
+            let (right, max) = delete_max(right);
+            node.right = right;
+            (Some(rebalance(node)), max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AvlTree, Tree};
+    use crate::SSet;
+
+    // the items of the root and of its children
+    fn top<T: Copy>(tree: &AvlTree<T>) -> (T, Option<T>, Option<T>) {
+        let root = tree.root.as_ref().expect("tree is not empty");
+        let item = |child: &Tree<T>| child.as_ref().map(|node| node.item);
+        (root.item, item(&root.left), item(&root.right))
+    }
+
+    fn tree_of(items: &[u32]) -> AvlTree<u32> {
+        let mut tree = AvlTree::new();
+        for item in items {
+            assert!(tree.add(*item));
+        }
+        assert!(tree.is_valid());
+        tree
+    }
+
+    #[test]
+    pub fn sset() {
+        crate::tests::sset(AvlTree::new());
+    }
+
+    #[test]
+    pub fn range() {
+        crate::tests::range(AvlTree::new());
+    }
+
+    #[test]
+    pub fn entry() {
+        crate::tests::entry(AvlTree::new());
+    }
+
+    #[test]
+    pub fn add_rotations() {
+        // right-right, left-left, left-right and right-left
+        for items in [[1, 2, 3], [3, 2, 1], [3, 1, 2], [1, 3, 2]].iter() {
+            let tree = tree_of(items);
+            assert_eq!(top(&tree), (2, Some(1), Some(3)), "{:?}", items);
+            assert_eq!(super::height(&tree.root), 2);
+        }
+    }
+
+    #[test]
+    pub fn remove_rotations() {
+        let cases = [
+            (&[2, 1, 3, 4], 1, (3, Some(2), Some(4))),
+            (&[3, 2, 4, 1], 4, (2, Some(1), Some(3))),
+            (&[3, 1, 4, 2], 4, (2, Some(1), Some(3))),
+            (&[2, 1, 4, 3], 1, (3, Some(2), Some(4))),
+            // the successor takes the place of a node with two children
+            (&[2, 1, 3, 4], 2, (3, Some(1), Some(4))),
+        ];
+        for (items, removed, expected) in cases.iter() {
+            let mut tree = tree_of(*items);
+            assert!(tree.remove(removed));
+            assert!(tree.is_valid());
+            assert_eq!(top(&tree), *expected, "{:?} - {}", items, removed);
+        }
+
+        let mut tree = tree_of(&[2, 1, 4, 3]);
+        assert_eq!(tree.pop_first(), Some(1));
+        assert_eq!(top(&tree), (3, Some(2), Some(4)));
+        let mut tree = tree_of(&[3, 1, 4, 2]);
+        assert_eq!(tree.pop_last(), Some(4));
+        assert_eq!(top(&tree), (2, Some(1), Some(3)));
+    }
+
+    #[test]
+    pub fn height_bound() {
+        // sorted insertions fill the levels one after the other
+        let mut tree = AvlTree::new();
+        for item in 1..1024 {
+            tree.add(item);
+            let levels = (usize::BITS - (item as usize).leading_zeros()) as usize;
+            assert_eq!(super::height(&tree.root), levels);
+        }
+        assert!(tree.is_valid());
+
+        // h < 1.4405 log2(n + 2) - 0.3277 holds for any AVL tree of n nodes
+        for item in (1..1024).filter(|item| item % 3 != 0) {
+            assert!(tree.remove(&item));
+            let bound = 1.4405 * ((tree.size() + 2) as f64).log2() - 0.3277;
+            assert!((super::height(&tree.root) as f64) < bound);
+        }
+        assert!(tree.is_valid());
+        assert_eq!(tree.size(), 341);
+    }
+
+    #[test]
+    pub fn invalid() {
+        let mut tree = tree_of(&[2, 1, 3]);
+        tree.root.as_mut().unwrap().height = 3;
+        assert!(!tree.is_valid());
+
+        let mut tree = tree_of(&[2, 1, 3]);
+        tree.root.as_mut().unwrap().item = 4;
+        assert!(!tree.is_valid());
+
+        let mut tree = tree_of(&[2, 1, 3]);
+        tree.size = 2;
+        assert!(!tree.is_valid());
+
+        // a chain of three is sorted but unbalanced
+        let mut tree = tree_of(&[1, 2]);
+        let mut right = tree.root.as_mut().unwrap().right.take().unwrap();
+        right.right = Some(super::Node::new(3));
+        right.height = 2;
+        let root = tree.root.as_mut().unwrap();
+        root.right = Some(right);
+        root.height = 3;
+        tree.size = 3;
+        assert!(!tree.is_valid());
+    }
+}
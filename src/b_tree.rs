@@ -0,0 +1,863 @@
+use crate::backend::{Array, Entry};
+use crate::CollectionError;
+use crate::INVALID_ARGUMENT_ERROR;
+use crate::{Range, SSet};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+const DEFAULT_BLOCK_SIZE: usize = 16;
+const MIN_BLOCK_SIZE: usize = 2;
+
+#[derive(Debug)]
+struct Node<T> {
+    // `key_count` keys in ascending order, then empty slots
+    keys: Array<T>,
+    // `key_count + 1` children on an internal node, none on a leaf
+    children: Array<usize>,
+    key_count: usize,
+    child_count: usize,
+}
+
+impl<T> Node<T> {
+    // room for one key over the limit, split right away
+    fn new(block_size: usize) -> Node<T> {
+        Node {
+            keys: Array::new(2 * block_size),
+            children: Array::new(2 * block_size + 1),
+            key_count: 0,
+            child_count: 0,
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.child_count == 0
+    }
+
+    fn key(&self, index: usize) -> &T {
+        match self.keys.get(index) {
+            Some(Entry::Item(key)) => key,
+            _ => unreachable!(),
+        }
+    }
+
+    fn child(&self, index: usize) -> usize {
+        match self.children.get(index) {
+            Some(Entry::Item(child)) => *child,
+            _ => unreachable!(),
+        }
+    }
+
+    // empties the slot, leaving the count to the caller
+    fn take_key(&mut self, index: usize) -> T {
+        match self.keys.remove(index) {
+            Some(Entry::Item(key)) => key,
+            _ => unreachable!(),
+        }
+    }
+
+    fn take_child(&mut self, index: usize) -> usize {
+        match self.children.remove(index) {
+            Some(Entry::Item(child)) => child,
+            _ => unreachable!(),
+        }
+    }
+
+    fn set_key(&mut self, index: usize, key: T) {
+        match self.keys.set(index, key) {
+            Ok(()) => (),
+            _ => unreachable!(),
+        }
+    }
+
+    fn set_child(&mut self, index: usize, child: usize) {
+        match self.children.set(index, child) {
+            Ok(()) => (),
+            _ => unreachable!(),
+        }
+    }
+
+    fn insert_key(&mut self, index: usize, key: T) {
+        self.keys.shift_right(index, self.key_count);
+        self.set_key(index, key);
+        self.key_count += 1;
+    }
+
+    fn insert_child(&mut self, index: usize, child: usize) {
+        self.children.shift_right(index, self.child_count);
+        self.set_child(index, child);
+        self.child_count += 1;
+    }
+
+    fn remove_key(&mut self, index: usize) -> T {
+        let key = self.take_key(index);
+        self.keys.shift_left(index, self.key_count - 1);
+        self.key_count -= 1;
+        key
+    }
+
+    fn remove_child(&mut self, index: usize) -> usize {
+        let child = self.take_child(index);
+        self.children.shift_left(index, self.child_count - 1);
+        self.child_count -= 1;
+        child
+    }
+
+    fn push_key(&mut self, key: T) {
+        self.set_key(self.key_count, key);
+        self.key_count += 1;
+    }
+
+    fn push_child(&mut self, child: usize) {
+        self.set_child(self.child_count, child);
+        self.child_count += 1;
+    }
+
+    fn replace_key(&mut self, index: usize, key: T) -> T {
+        let replaced = self.take_key(index);
+        self.set_key(index, key);
+        replaced
+    }
+}
+
+enum Insertion<T> {
+    Present,
+    Added,
+    // the node was split around the key, the new right node after it
+    Split(T, usize),
+}
+
+/// Every node but the root holds from `B - 1` to `2B - 1` keys in a block of fixed size, so an
+/// operation visits O(log_B n) blocks, searching each by bisection in O(log B). Queries take
+/// O(log n), `add` and `take` O(B log_B n) as they shift the keys of a block, `range`
+/// O(log n + k) for k items. The blocks are kept in one arena and addressed by index, as blocks of
+/// external memory would be.
+#[derive(Debug)]
+pub struct BTree<T> {
+    nodes: Vec<Node<T>>,
+    // the unused slots of `nodes`
+    free: Vec<usize>,
+    root: usize,
+    block_size: usize,
+    size: usize,
+}
+
+impl<T: Ord> BTree<T> {
+    pub fn new() -> BTree<T> {
+        BTree {
+            nodes: vec![Node::new(DEFAULT_BLOCK_SIZE)],
+            free: vec![],
+            root: 0,
+            block_size: DEFAULT_BLOCK_SIZE,
+            size: 0,
+        }
+    }
+
+    /// Nodes of `B - 1` to `2B - 1` keys for the block size `B`, at least 2.
+    pub fn with_block_size(block_size: usize) -> Result<BTree<T>, CollectionError> {
+        if block_size < MIN_BLOCK_SIZE {
+            return Err(INVALID_ARGUMENT_ERROR);
+        }
+        Ok(BTree {
+            nodes: vec![Node::new(block_size)],
+            block_size,
+            ..Self::new()
+        })
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Checks that the keys are sorted, every node but the root holds from `B - 1` to `2B - 1`
+    /// of them and every leaf is at the same depth. O(n).
+    pub fn is_valid(&self) -> bool {
+        match self.check(self.root, None, None) {
+            Some((_, count)) => count == self.size,
+            None => false,
+        }
+    }
+
+    // the height and the number of keys of a valid subtree with keys between `low` and `high`
+    fn check(&self, node: usize, low: Option<&T>, high: Option<&T>) -> Option<(usize, usize)> {
+        let current = &self.nodes[node];
+        let b = self.block_size;
+        if current.key_count > 2 * b - 1 || (node != self.root && current.key_count < b - 1) {
+            return None;
+        }
+        if !current.is_leaf() && current.child_count != current.key_count + 1 {
+            return None;
+        }
+        if (current.key_count..current.keys.len())
+            .any(|index| current.keys.get(index) != Some(&Entry::Empty))
+        {
+            return None;
+        }
+
+        let bound = |index: usize| -> Option<&T> {
+            match index {
+                0 => low,
+                _ if index > current.key_count => high,
+                _ => Some(current.key(index - 1)),
+            }
+        };
+        for index in 0..=current.key_count {
+            if let (Some(before), Some(after)) = (bound(index), bound(index + 1)) {
+                if before >= after {
+                    return None;
+                }
+            }
+        }
+
+        if current.is_leaf() {
+            return Some((0, current.key_count));
+        }
+        let mut height = None;
+        let mut count = current.key_count;
+        for index in 0..current.child_count {
+            let (child_height, child_count) =
+                self.check(current.child(index), bound(index), bound(index + 1))?;
+            if height.is_some_and(|height| height != child_height) {
+                return None;
+            }
+            height = Some(child_height);
+            count += child_count;
+        }
+        Some((height? + 1, count))
+    }
+
+    // the index of the key equal to `item`, or of the first greater key
+    fn search<Q>(&self, node: usize, item: &Q) -> Result<usize, usize>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let node = &self.nodes[node];
+        let (mut low, mut high) = (0, node.key_count);
+        while low < high {
+            let middle = (low + high) / 2;
+            match node.key(middle).borrow().cmp(item) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Equal => return Ok(middle),
+                Ordering::Greater => high = middle,
+            }
+        }
+        Err(low)
+    }
+
+    fn allocate(&mut self, node: Node<T>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn add_to(&mut self, node: usize, item: T) -> Insertion<T> {
+        let index = match self.search(node, &item) {
+            Ok(_) => return Insertion::Present,
+            Err(index) => index,
+        };
+
+        if self.nodes[node].is_leaf() {
+            self.nodes[node].insert_key(index, item);
+        } else {
+            match self.add_to(self.nodes[node].child(index), item) {
+                Insertion::Split(key, right) => {
+                    self.nodes[node].insert_key(index, key);
+                    self.nodes[node].insert_child(index + 1, right);
+                }
+                insertion => return insertion,
+            }
+        }
+
+        if self.nodes[node].key_count < 2 * self.block_size {
+            return Insertion::Added;
+        }
+        let (key, right) = self.split(node);
+        Insertion::Split(key, right)
+    }
+
+    // moves the upper half of the full `node` to a new node, returning the middle key between them
+    fn split(&mut self, node: usize) -> (T, usize) {
+        let b = self.block_size;
+        let mut right = Node::new(b);
+
+        let left = &mut self.nodes[node];
+        for index in b + 1..2 * b {
+            right.push_key(left.take_key(index));
+        }
+        if !left.is_leaf() {
+            for index in b + 1..=2 * b {
+                right.push_child(left.take_child(index));
+            }
+            left.child_count = b + 1;
+        }
+        left.key_count = b + 1;
+        let key = left.remove_key(b);
+
+        (key, self.allocate(right))
+    }
+
+    fn take_from<Q>(&mut self, node: usize, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let is_leaf = self.nodes[node].is_leaf();
+        match self.search(node, item) {
+            Ok(index) if is_leaf => Some(self.nodes[node].remove_key(index)),
+            Ok(index) => {
+                // the next key takes the place of the removed one
+                let next = self.pop_first_from(self.nodes[node].child(index + 1));
+                let key = self.nodes[node].replace_key(index, next);
+                self.fix_underflow(node, index + 1);
+                Some(key)
+            }
+            Err(_) if is_leaf => None,
+            Err(index) => {
+                let key = self.take_from(self.nodes[node].child(index), item)?;
+                self.fix_underflow(node, index);
+                Some(key)
+            }
+        }
+    }
+
+    fn pop_first_from(&mut self, node: usize) -> T {
+        if self.nodes[node].is_leaf() {
+            return self.nodes[node].remove_key(0);
+        }
+        let key = self.pop_first_from(self.nodes[node].child(0));
+        self.fix_underflow(node, 0);
+        key
+    }
+
+    fn pop_last_from(&mut self, node: usize) -> T {
+        let current = &self.nodes[node];
+        if current.is_leaf() {
+            let last = current.key_count - 1;
+            return self.nodes[node].remove_key(last);
+        }
+        let last = current.child_count - 1;
+        let key = self.pop_last_from(current.child(last));
+        self.fix_underflow(node, last);
+        key
+    }
+
+    // refills the child at `index` of `node` when it is left with less than `B - 1` keys
+    fn fix_underflow(&mut self, node: usize, index: usize) {
+        let b = self.block_size;
+        let current = &self.nodes[node];
+        if self.nodes[current.child(index)].key_count >= b - 1 {
+            return;
+        }
+
+        if index > 0 && self.nodes[current.child(index - 1)].key_count > b - 1 {
+            self.borrow_from_left(node, index);
+        } else if index < current.key_count
+            && self.nodes[current.child(index + 1)].key_count > b - 1
+        {
+            self.borrow_from_right(node, index);
+        } else if index > 0 {
+            self.merge(node, index - 1);
+        } else {
+            self.merge(node, index);
+        }
+    }
+
+    // rotates the last key of the left sibling through `node`
+    fn borrow_from_left(&mut self, node: usize, index: usize) {
+        let left = self.nodes[node].child(index - 1);
+        let child = self.nodes[node].child(index);
+
+        let last = self.nodes[left].key_count - 1;
+        let key = self.nodes[left].remove_key(last);
+        let key = self.nodes[node].replace_key(index - 1, key);
+        self.nodes[child].insert_key(0, key);
+
+        if !self.nodes[left].is_leaf() {
+            let last = self.nodes[left].child_count - 1;
+            let grandchild = self.nodes[left].remove_child(last);
+            self.nodes[child].insert_child(0, grandchild);
+        }
+    }
+
+    // rotates the first key of the right sibling through `node`
+    fn borrow_from_right(&mut self, node: usize, index: usize) {
+        let child = self.nodes[node].child(index);
+        let right = self.nodes[node].child(index + 1);
+
+        let key = self.nodes[right].remove_key(0);
+        let key = self.nodes[node].replace_key(index, key);
+        self.nodes[child].push_key(key);
+
+        if !self.nodes[right].is_leaf() {
+            let grandchild = self.nodes[right].remove_child(0);
+            self.nodes[child].push_child(grandchild);
+        }
+    }
+
+    // joins the children at `index` and `index + 1` around the key between them
+    fn merge(&mut self, node: usize, index: usize) {
+        let left = self.nodes[node].child(index);
+        let right = self.nodes[node].remove_child(index + 1);
+        let key = self.nodes[node].remove_key(index);
+        self.nodes[left].push_key(key);
+
+        for index in 0..self.nodes[right].key_count {
+            let key = self.nodes[right].take_key(index);
+            self.nodes[left].push_key(key);
+        }
+        for index in 0..self.nodes[right].child_count {
+            let child = self.nodes[right].take_child(index);
+            self.nodes[left].push_child(child);
+        }
+        self.nodes[right].key_count = 0;
+        self.nodes[right].child_count = 0;
+        self.free.push(right);
+    }
+
+    // drops an emptied root for its only child
+    fn shrink(&mut self) {
+        let root = &self.nodes[self.root];
+        if root.key_count == 0 && !root.is_leaf() {
+            let child = self.nodes[self.root].remove_child(0);
+            self.free.push(self.root);
+            self.root = child;
+        }
+    }
+}
+
+impl<T: Ord> Default for BTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> SSet<T> for BTree<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, item: T) -> bool {
+        match self.add_to(self.root, item) {
+            Insertion::Present => return false,
+            Insertion::Added => (),
+            Insertion::Split(key, right) => {
+                let mut root = Node::new(self.block_size);
+                root.push_key(key);
+                root.push_child(self.root);
+                root.push_child(right);
+                self.root = self.allocate(root);
+            }
+        }
+        self.size += 1;
+        true
+    }
+
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let key = self.take_from(self.root, item)?;
+        self.shrink();
+        self.size -= 1;
+        Some(key)
+    }
+
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut found = None;
+        let mut node = self.root;
+        loop {
+            let current = &self.nodes[node];
+            let index = match self.search(node, item) {
+                Ok(index) => return Some(current.key(index)),
+                Err(index) => index,
+            };
+            if index < current.key_count {
+                found = Some(current.key(index));
+            }
+            if current.is_leaf() {
+                return found;
+            }
+            node = current.child(index);
+        }
+    }
+
+    fn successor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut found = None;
+        let mut node = self.root;
+        loop {
+            let current = &self.nodes[node];
+            let index = match self.search(node, item) {
+                Ok(index) => index + 1,
+                Err(index) => index,
+            };
+            if index < current.key_count {
+                found = Some(current.key(index));
+            }
+            if current.is_leaf() {
+                return found;
+            }
+            node = current.child(index);
+        }
+    }
+
+    fn predecessor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut found = None;
+        let mut node = self.root;
+        loop {
+            let current = &self.nodes[node];
+            let index = match self.search(node, item) {
+                Ok(index) | Err(index) => index,
+            };
+            if index > 0 {
+                found = Some(current.key(index - 1));
+            }
+            if current.is_leaf() {
+                return found;
+            }
+            node = current.child(index);
+        }
+    }
+
+    fn first(&self) -> Option<&T> {
+        if self.size == 0 {
+            return None;
+        }
+        let mut node = &self.nodes[self.root];
+        while !node.is_leaf() {
+            node = &self.nodes[node.child(0)];
+        }
+        Some(node.key(0))
+    }
+
+    fn last(&self) -> Option<&T> {
+        if self.size == 0 {
+            return None;
+        }
+        let mut node = &self.nodes[self.root];
+        while !node.is_leaf() {
+            node = &self.nodes[node.child(node.child_count - 1)];
+        }
+        Some(node.key(node.key_count - 1))
+    }
+
+    fn pop_first(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+        let key = self.pop_first_from(self.root);
+        self.shrink();
+        self.size -= 1;
+        Some(key)
+    }
+
+    fn pop_last(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+        let key = self.pop_last_from(self.root);
+        self.shrink();
+        self.size -= 1;
+        Some(key)
+    }
+
+    fn range<Q, R>(&self, bounds: R) -> Range<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let mut ascending = Walk {
+            tree: self,
+            stack: vec![],
+            ascending: true,
+        };
+        let mut node = self.root;
+        loop {
+            let current = &self.nodes[node];
+            // the first key within the lower bound
+            let index = match bounds.start_bound() {
+                Bound::Included(start) => match self.search(node, start) {
+                    Ok(index) | Err(index) => index,
+                },
+                Bound::Excluded(start) => match self.search(node, start) {
+                    Ok(index) => index + 1,
+                    Err(index) => index,
+                },
+                Bound::Unbounded => 0,
+            };
+            if index < current.key_count {
+                ascending.stack.push((node, index));
+            }
+            if current.is_leaf() {
+                break;
+            }
+            node = current.child(index);
+        }
+
+        let mut descending = Walk {
+            tree: self,
+            stack: vec![],
+            ascending: false,
+        };
+        let mut node = self.root;
+        loop {
+            let current = &self.nodes[node];
+            // the number of keys within the upper bound
+            let count = match bounds.end_bound() {
+                Bound::Included(end) => match self.search(node, end) {
+                    Ok(index) => index + 1,
+                    Err(index) => index,
+                },
+                Bound::Excluded(end) => match self.search(node, end) {
+                    Ok(index) | Err(index) => index,
+                },
+                Bound::Unbounded => current.key_count,
+            };
+            if count > 0 {
+                descending.stack.push((node, count - 1));
+            }
+            if current.is_leaf() {
+                break;
+            }
+            node = current.child(count);
+        }
+
+        Range::new(ascending, descending)
+    }
+}
+
+// the top of `stack` is the node and index of the next key, below it the keys of the ancestors
+// that come after it
+struct Walk<'a, T> {
+    tree: &'a BTree<T>,
+    stack: Vec<(usize, usize)>,
+    ascending: bool,
+}
+
+impl<'a, T> Walk<'a, T> {
+    // pushes the path to the first key of the subtree in the walking order
+    fn descend(&mut self, mut node: usize) {
+        let tree = self.tree;
+        loop {
+            let current = &tree.nodes[node];
+            if self.ascending {
+                self.stack.push((node, 0));
+            } else {
+                self.stack.push((node, current.key_count - 1));
+            }
+            if current.is_leaf() {
+                return;
+            }
+            node = if self.ascending {
+                current.child(0)
+            } else {
+                current.child(current.child_count - 1)
+            };
+        }
+    }
+}
+
+impl<'a, T> Iterator for Walk<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (node, index) = self.stack.pop()?;
+        let tree = self.tree;
+        let current = &tree.nodes[node];
+        if self.ascending {
+            if index + 1 < current.key_count {
+                self.stack.push((node, index + 1));
+            }
+            if !current.is_leaf() {
+                self.descend(current.child(index + 1));
+            }
+        } else {
+            if index > 0 {
+                self.stack.push((node, index - 1));
+            }
+            if !current.is_leaf() {
+                self.descend(current.child(index));
+            }
+        }
+        Some(current.key(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BTree;
+    use crate::SSet;
+    use crate::INVALID_ARGUMENT_ERROR;
+
+    fn keys(tree: &BTree<u32>, node: usize) -> Vec<u32> {
+        let node = &tree.nodes[node];
+        (0..node.key_count).map(|index| *node.key(index)).collect()
+    }
+
+    // the keys of the children of the root
+    fn leaves(tree: &BTree<u32>) -> Vec<Vec<u32>> {
+        let root = &tree.nodes[tree.root];
+        (0..root.child_count)
+            .map(|index| keys(tree, root.child(index)))
+            .collect()
+    }
+
+    #[test]
+    pub fn sset() {
+        crate::tests::sset(BTree::with_block_size(2).unwrap());
+    }
+
+    #[test]
+    pub fn root() {
+        assert_eq!(
+            BTree::<u32>::with_block_size(1).err(),
+            Some(INVALID_ARGUMENT_ERROR)
+        );
+
+        // a full root leaf splits around its middle key
+        let mut tree = BTree::with_block_size(2).unwrap();
+        for item in 1..=3 {
+            tree.add(item);
+        }
+        assert!(tree.nodes[tree.root].is_leaf());
+        tree.add(4);
+        assert_eq!(keys(&tree, tree.root), vec![3]);
+        assert_eq!(leaves(&tree), vec![vec![1, 2], vec![4]]);
+
+        // a root emptied by a merge gives way to its only child
+        let root = tree.root;
+        assert!(tree.remove(&4));
+        assert!(tree.remove(&3));
+        assert!(tree.nodes[tree.root].is_leaf());
+        assert_eq!(keys(&tree, tree.root), vec![1, 2]);
+        assert!(tree.free.contains(&root));
+        assert_eq!(tree.nodes.len() - tree.free.len(), 1);
+        assert!(tree.is_valid());
+    }
+
+    #[test]
+    pub fn underflow() {
+        let mut tree = BTree::with_block_size(2).unwrap();
+        for item in 1..=5 {
+            tree.add(item);
+        }
+        assert_eq!(leaves(&tree), vec![vec![1, 2], vec![4, 5]]);
+
+        // a sibling with a key to spare lends it through the parent
+        assert!(tree.remove(&1));
+        assert!(tree.remove(&2));
+        assert_eq!(keys(&tree, tree.root), vec![4]);
+        assert_eq!(leaves(&tree), vec![vec![3], vec![5]]);
+        tree.add(1);
+        assert!(tree.remove(&5));
+        assert_eq!(keys(&tree, tree.root), vec![3]);
+        assert_eq!(leaves(&tree), vec![vec![1], vec![4]]);
+
+        // else the two siblings merge
+        tree.add(5);
+        tree.add(6);
+        tree.add(7);
+        assert_eq!(leaves(&tree), vec![vec![1], vec![4, 5], vec![7]]);
+        assert!(tree.remove(&5));
+        assert!(tree.remove(&4));
+        assert_eq!(keys(&tree, tree.root), vec![6]);
+        assert_eq!(leaves(&tree), vec![vec![1, 3], vec![7]]);
+        assert_eq!(tree.free.len(), 1);
+
+        // internal nodes borrow and merge with their children
+        for block_size in [2, 3, 8] {
+            let mut tree = BTree::with_block_size(block_size).unwrap();
+            for item in 0..500 {
+                tree.add(item);
+            }
+            for item in (0..500).filter(|item| item % 3 != 0) {
+                assert!(tree.remove(&item));
+                assert!(tree.is_valid());
+            }
+            for item in (0..500).filter(|item| item % 3 == 0).rev() {
+                assert!(tree.remove(&item));
+                assert!(tree.is_valid());
+            }
+            assert_eq!(tree.size(), 0);
+            assert_eq!(tree.nodes.len() - tree.free.len(), 1);
+        }
+    }
+
+    #[test]
+    pub fn ordered() {
+        let mut tree = BTree::new();
+        assert_eq!(tree.block_size(), 16);
+        assert_eq!(tree.first(), None);
+        assert_eq!(tree.pop_last(), None);
+
+        for item in (0..1000).rev() {
+            assert!(tree.add(item * 2));
+        }
+        assert!(tree.is_valid());
+        assert_eq!(tree.first(), Some(&0));
+        assert_eq!(tree.last(), Some(&1998));
+        for item in 0..999 {
+            assert_eq!(tree.successor(&(item * 2)), Some(&(item * 2 + 2)));
+            assert_eq!(tree.find(&(item * 2 + 1)), Some(&(item * 2 + 2)));
+            assert_eq!(tree.predecessor(&(item * 2 + 1)), Some(&(item * 2)));
+        }
+        assert_eq!(tree.count_in_range(100..200), 50);
+
+        for item in 0..500 {
+            assert_eq!(tree.pop_first(), Some(item * 2));
+            assert_eq!(tree.pop_last(), Some(1998 - item * 2));
+        }
+        assert_eq!(tree.pop_first(), None);
+        assert!(tree.is_valid());
+    }
+
+    #[test]
+    pub fn range() {
+        let mut tree = BTree::with_block_size(2).unwrap();
+        assert_eq!(tree.range(..).next(), None);
+
+        for item in 0..100 {
+            tree.add(item * 2);
+        }
+
+        // the ends cross leaves and internal nodes
+        let items: Vec<&i32> = tree.range(31..=41).collect();
+        assert_eq!(items, vec![&32, &34, &36, &38, &40]);
+        let items: Vec<&i32> = tree.range(31..41).rev().collect();
+        assert_eq!(items, vec![&40, &38, &36, &34, &32]);
+
+        let mut items = tree.range(..);
+        for item in 0..50 {
+            assert_eq!(items.next(), Some(&(item * 2)));
+            assert_eq!(items.next_back(), Some(&(198 - item * 2)));
+        }
+        assert_eq!(items.next(), None);
+        assert_eq!(items.next_back(), None);
+
+        assert_eq!(tree.count_in_range(..=0), 1);
+        assert_eq!(tree.count_in_range(198..), 1);
+        assert_eq!(tree.count_in_range(199..), 0);
+        assert_eq!(tree.count_in_range(33..35), 1);
+    }
+}
@@ -32,7 +32,7 @@ impl<T> Array<T> {
 
     pub(in crate) fn get(&self, index: usize) -> Option<&Entry<T>> {
         if let Some(entry) = self.items.get(index) {
-            Some(&entry)
+            Some(entry)
         } else {
             None
         }
@@ -57,6 +57,10 @@ impl<T> Array<T> {
         }
     }
 
+    pub(in crate) fn swap(&mut self, a: usize, b: usize) {
+        self.items.swap(a, b);
+    }
+
     pub(in crate) fn shift_right(&mut self, from: usize, to: usize) {
         let mut target_index = to;
         while target_index > from {
@@ -95,18 +99,13 @@ impl<T> Array<T> {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
 pub(in crate) enum Entry<T> {
+    #[default]
     Empty,
     Item(T),
 }
 
-impl<T> Default for Entry<T> {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 #[derive(Debug, PartialEq, Eq)]
 pub(in crate) struct BackendError {
     message: &'static str,
@@ -188,6 +187,22 @@ mod tests {
         assert_eq!(array.get(5), None);
     }
 
+    #[test]
+    pub fn swap() {
+        let length = 3;
+        let mut array = Array::new(length);
+
+        assert_eq!(array.set(0, 1), Ok(()));
+        assert_eq!(array.set(2, 3), Ok(()));
+
+        array.swap(0, 1);
+        array.swap(1, 2);
+
+        assert_eq!(array.get(0), Some(&Entry::Empty));
+        assert_eq!(array.get(1), Some(&Entry::Item(3)));
+        assert_eq!(array.get(2), Some(&Entry::Item(1)));
+    }
+
     #[test]
     pub fn shift_right() {
         let length = 5;
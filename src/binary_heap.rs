@@ -0,0 +1,300 @@
+use crate::backend::{Array, Entry};
+use crate::PriorityQueue;
+use std::iter::FromIterator;
+
+const DEFAULT_BACKEND_SIZE: usize = 2;
+const SIZE_UP_MULTIPLIER_NUMBER: usize = 2;
+const SIZE_DOWN_THRESHOLD: usize = 3;
+const SIZE_DOWN_DIVISION_NUMBER: usize = 2;
+
+#[derive(Debug)]
+struct Slot<T> {
+    item: T,
+    order: u64,
+}
+
+#[derive(Debug)]
+pub struct BinaryHeap<T> {
+    backend: Array<Slot<T>>,
+    size: usize,
+    fifo_ties: bool,
+    order: u64,
+}
+
+impl<T: Ord> BinaryHeap<T> {
+    pub fn new() -> BinaryHeap<T> {
+        BinaryHeap {
+            backend: Array::new(DEFAULT_BACKEND_SIZE),
+            size: 0,
+            fifo_ties: false,
+            order: 0,
+        }
+    }
+
+    /// Equal items are removed in the order they were added.
+    pub fn with_fifo_ties() -> BinaryHeap<T> {
+        BinaryHeap {
+            fifo_ties: true,
+            ..Self::new()
+        }
+    }
+
+    /// Builds the heap bottom-up in O(n).
+    pub fn from_iter_with_fifo_ties<I: IntoIterator<Item = T>>(items: I) -> BinaryHeap<T> {
+        let mut heap = Self::with_fifo_ties();
+        heap.heapify(items);
+        heap
+    }
+
+    fn heapify<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        let items: Vec<T> = items.into_iter().collect();
+
+        let mut backend_len = DEFAULT_BACKEND_SIZE;
+        while backend_len < items.len() {
+            backend_len *= SIZE_UP_MULTIPLIER_NUMBER;
+        }
+        self.backend = Array::new(backend_len);
+
+        for item in items {
+            self.set(self.size, item);
+            self.size += 1;
+        }
+
+        let mut index = self.size / 2;
+        while index > 0 {
+            index -= 1;
+            self.trickle_down(index);
+        }
+    }
+
+    fn slot(&self, index: usize) -> &Slot<T> {
+        match self.backend.get(index) {
+            Some(Entry::Item(slot)) => slot,
+            _ => unreachable!(),
+        }
+    }
+
+    fn set(&mut self, index: usize, item: T) {
+        let slot = Slot {
+            item,
+            order: self.order,
+        };
+        self.order += 1;
+
+        match self.backend.set(index, slot) {
+            Ok(()) => (),
+            _ => unreachable!(),
+        }
+    }
+
+    fn is_less(&self, a: usize, b: usize) -> bool {
+        let a = self.slot(a);
+        let b = self.slot(b);
+        a.item < b.item || (self.fifo_ties && a.item == b.item && a.order < b.order)
+    }
+
+    fn bubble_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.is_less(index, parent) {
+                break;
+            }
+            self.backend.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn trickle_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+
+            let mut smallest = index;
+            if left < self.size && self.is_less(left, smallest) {
+                smallest = left;
+            }
+            if right < self.size && self.is_less(right, smallest) {
+                smallest = right;
+            }
+            if smallest == index {
+                break;
+            }
+
+            self.backend.swap(index, smallest);
+            index = smallest;
+        }
+    }
+}
+
+impl<T: Ord> Default for BinaryHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinaryHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut heap = Self::new();
+        heap.heapify(items);
+        heap
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for BinaryHeap<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, x: T) {
+        if self.is_size_up_required() {
+            self.size_up();
+        }
+
+        self.set(self.size, x);
+        self.size += 1;
+
+        self.bubble_up(self.size - 1);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        if self.size == 0 {
+            return None;
+        }
+
+        Some(&self.slot(0).item)
+    }
+
+    fn remove_min(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+
+        self.size -= 1;
+        self.backend.swap(0, self.size);
+
+        match self.backend.remove(self.size) {
+            Some(Entry::Item(slot)) => {
+                self.trickle_down(0);
+
+                if self.is_size_down_required() {
+                    self.size_down();
+                }
+
+                Some(slot.item)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<T> BinaryHeap<T> {
+    fn backend_len(&self) -> usize {
+        self.backend.len()
+    }
+
+    fn is_size_up_required(&self) -> bool {
+        self.size == self.backend_len()
+    }
+
+    fn is_size_down_required(&self) -> bool {
+        self.size * SIZE_DOWN_THRESHOLD < self.backend_len()
+            && self.backend_len() > DEFAULT_BACKEND_SIZE
+    }
+
+    fn size_up(&mut self) {
+        self.backend
+            .resize(self.backend_len() * SIZE_UP_MULTIPLIER_NUMBER, 0, self.size);
+    }
+
+    fn size_down(&mut self) {
+        self.backend
+            .resize(self.backend_len() / SIZE_DOWN_DIVISION_NUMBER, 0, self.size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryHeap;
+    use crate::PriorityQueue;
+    use std::cmp::Ordering;
+
+    #[test]
+    pub fn priority_queue() {
+        crate::tests::priority_queue(BinaryHeap::new());
+    }
+
+    #[test]
+    pub fn heapify() {
+        let mut heap: BinaryHeap<i32> = vec![9, 2, 7, 4, 5, 6, 3, 8, 1].into_iter().collect();
+        assert_eq!(heap.size(), 9);
+
+        let mut items = vec![];
+        while let Some(item) = heap.remove_min() {
+            items.push(item);
+        }
+        assert_eq!(items, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[derive(Debug)]
+    struct Task {
+        priority: u32,
+        name: &'static str,
+    }
+
+    impl PartialEq for Task {
+        fn eq(&self, other: &Self) -> bool {
+            self.priority == other.priority
+        }
+    }
+
+    impl Eq for Task {}
+
+    impl PartialOrd for Task {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Task {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.priority.cmp(&other.priority)
+        }
+    }
+
+    #[test]
+    pub fn fifo_ties() {
+        let tasks = vec![
+            Task {
+                priority: 2,
+                name: "a",
+            },
+            Task {
+                priority: 1,
+                name: "b",
+            },
+            Task {
+                priority: 2,
+                name: "c",
+            },
+            Task {
+                priority: 1,
+                name: "d",
+            },
+            Task {
+                priority: 2,
+                name: "e",
+            },
+        ];
+        let mut heap = BinaryHeap::from_iter_with_fifo_ties(tasks);
+        heap.add(Task {
+            priority: 1,
+            name: "f",
+        });
+
+        let mut names = vec![];
+        while let Some(task) = heap.remove_min() {
+            names.push(task.name);
+        }
+        assert_eq!(names, vec!["b", "d", "f", "a", "c", "e"]);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::Range;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+/// A node of a binary search tree: every item on the left is less than `item`, every item on
+/// the right is greater.
+pub(in crate) trait SearchNode {
+    type Item;
+
+    fn item(&self) -> &Self::Item;
+    fn left(&self) -> Option<&Self>;
+    fn right(&self) -> Option<&Self>;
+}
+
+pub(in crate) fn find<'a, Q, N>(root: Option<&'a N>, item: &Q) -> Option<&'a N::Item>
+where
+    N: SearchNode,
+    N::Item: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut found = None;
+    let mut node = root;
+    while let Some(current) = node {
+        match item.cmp(current.item().borrow()) {
+            Ordering::Less => {
+                found = Some(current.item());
+                node = current.left();
+            }
+            Ordering::Greater => node = current.right(),
+            Ordering::Equal => return Some(current.item()),
+        }
+    }
+    found
+}
+
+pub(in crate) fn successor<'a, Q, N>(root: Option<&'a N>, item: &Q) -> Option<&'a N::Item>
+where
+    N: SearchNode,
+    N::Item: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut found = None;
+    let mut node = root;
+    while let Some(current) = node {
+        if item < current.item().borrow() {
+            found = Some(current.item());
+            node = current.left();
+        } else {
+            node = current.right();
+        }
+    }
+    found
+}
+
+pub(in crate) fn predecessor<'a, Q, N>(root: Option<&'a N>, item: &Q) -> Option<&'a N::Item>
+where
+    N: SearchNode,
+    N::Item: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut found = None;
+    let mut node = root;
+    while let Some(current) = node {
+        if item > current.item().borrow() {
+            found = Some(current.item());
+            node = current.right();
+        } else {
+            node = current.left();
+        }
+    }
+    found
+}
+
+pub(in crate) fn first<N: SearchNode>(root: Option<&N>) -> Option<&N::Item> {
+    let mut node = root?;
+    while let Some(left) = node.left() {
+        node = left;
+    }
+    Some(node.item())
+}
+
+pub(in crate) fn last<N: SearchNode>(root: Option<&N>) -> Option<&N::Item> {
+    let mut node = root?;
+    while let Some(right) = node.right() {
+        node = right;
+    }
+    Some(node.item())
+}
+
+/// Items within `bounds`, each visited node popped once from one of two stacks of the nodes left
+/// to visit. O(log n + k) for k items.
+pub(in crate) fn range<'a, Q, R, N>(root: Option<&'a N>, bounds: R) -> Range<'a, N::Item>
+where
+    N: SearchNode,
+    N::Item: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+    R: RangeBounds<Q>,
+{
+    let mut ascending = Walk {
+        stack: vec![],
+        ascending: true,
+    };
+    let mut node = root;
+    while let Some(current) = node {
+        let item = current.item().borrow();
+        let inside = match bounds.start_bound() {
+            Bound::Included(start) => item >= start,
+            Bound::Excluded(start) => item > start,
+            Bound::Unbounded => true,
+        };
+        if inside {
+            ascending.stack.push(current);
+            node = current.left();
+        } else {
+            node = current.right();
+        }
+    }
+
+    let mut descending = Walk {
+        stack: vec![],
+        ascending: false,
+    };
+    let mut node = root;
+    while let Some(current) = node {
+        let item = current.item().borrow();
+        let inside = match bounds.end_bound() {
+            Bound::Included(end) => item <= end,
+            Bound::Excluded(end) => item < end,
+            Bound::Unbounded => true,
+        };
+        if inside {
+            descending.stack.push(current);
+            node = current.right();
+        } else {
+            node = current.left();
+        }
+    }
+
+    Range::new(ascending, descending)
+}
+
+// the top of `stack` is the next node, below it the ancestors whose item comes after it
+struct Walk<'a, N> {
+    stack: Vec<&'a N>,
+    ascending: bool,
+}
+
+impl<'a, N: SearchNode> Iterator for Walk<'a, N> {
+    type Item = &'a N::Item;
+
+    fn next(&mut self) -> Option<&'a N::Item> {
+        let node = self.stack.pop()?;
+        let mut child = if self.ascending {
+            node.right()
+        } else {
+            node.left()
+        };
+        while let Some(current) = child {
+            self.stack.push(current);
+            child = if self.ascending {
+                current.left()
+            } else {
+                current.right()
+            };
+        }
+        Some(node.item())
+    }
+}
@@ -0,0 +1,418 @@
+use crate::SSet;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+const ROOT: usize = 0;
+
+/// A `W`-bit unsigned integer key.
+pub trait Word: Copy + Ord {
+    const W: u32;
+    const MAX: u64 = u64::MAX >> (u64::BITS - Self::W);
+
+    fn to_u64(self) -> u64;
+
+    /// The low `W` bits of `x`.
+    fn from_u64(x: u64) -> Self;
+
+    /// The bit at `depth`, counting from the most significant bit at depth 0.
+    fn bit(self, depth: u32) -> usize {
+        ((self.to_u64() >> (Self::W - 1 - depth)) & 1) as usize
+    }
+}
+
+/// The least word not less than `item`, and whether it equals `item`, by bisection over the words:
+/// W comparisons that touch no memory, for the structures needing the bits of a query known only
+/// by its order.
+pub(in crate) fn locate<T, Q>(item: &Q) -> Option<(u64, bool)>
+where
+    T: Word + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let is_before = |key: u64| <T as Borrow<Q>>::borrow(&T::from_u64(key)) < item;
+
+    let (mut low, mut high) = (0, T::MAX);
+    if is_before(high) {
+        return None;
+    }
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if is_before(middle) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    Some((low, <T as Borrow<Q>>::borrow(&T::from_u64(low)) == item))
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const W: u32 = <$t>::BITS;
+
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+
+                fn from_u64(x: u64) -> Self {
+                    x as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8, u16, u32, u64);
+
+#[derive(Debug)]
+struct Node<T> {
+    parent: Option<usize>,
+    children: [Option<usize>; 2],
+    // the jump pointers to the least and the greatest leaf of the subtree
+    min: usize,
+    max: usize,
+    // the leaf list, on leaves only
+    prev: Option<usize>,
+    next: Option<usize>,
+    item: Option<T>,
+}
+
+impl<T> Node<T> {
+    fn new(parent: Option<usize>) -> Node<T> {
+        Node {
+            parent,
+            children: [None, None],
+            min: 0,
+            max: 0,
+            prev: None,
+            next: None,
+            item: None,
+        }
+    }
+}
+
+/// The items are the leaves of a binary tree of height `W` whose edges spell their bits. Every
+/// operation is O(W), independent of the number of items, and `range` O(W) per item.
+#[derive(Debug)]
+pub struct BinaryTrie<T> {
+    nodes: Vec<Node<T>>,
+    // the unused slots of `nodes`
+    free: Vec<usize>,
+    size: usize,
+}
+
+impl<T: Word> BinaryTrie<T> {
+    pub fn new() -> BinaryTrie<T> {
+        BinaryTrie {
+            nodes: vec![Node::new(None)],
+            free: vec![],
+            size: 0,
+        }
+    }
+
+    fn item(&self, leaf: usize) -> &T {
+        match &self.nodes[leaf].item {
+            Some(item) => item,
+            _ => unreachable!(),
+        }
+    }
+
+    // the least leaf with an item greater than or equal to `item`, or strictly greater
+    fn search<Q>(&self, item: &Q, is_strict: bool) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.size == 0 {
+            return None;
+        }
+
+        let is_before = |leaf: usize| match self.item(leaf).borrow().cmp(item) {
+            Ordering::Less => true,
+            Ordering::Equal => is_strict,
+            Ordering::Greater => false,
+        };
+
+        // the items of `node` are the answer or precede it, and some of them precede it
+        let mut node = ROOT;
+        loop {
+            let Node { min, max, .. } = self.nodes[node];
+            if is_before(max) {
+                return self.nodes[max].next;
+            }
+            if !is_before(min) {
+                return Some(min);
+            }
+
+            node = match self.nodes[node].children {
+                [Some(left), Some(right)] => {
+                    if is_before(self.nodes[left].max) {
+                        right
+                    } else {
+                        left
+                    }
+                }
+                [Some(child), None] | [None, Some(child)] => child,
+                [None, None] => unreachable!(),
+            };
+        }
+    }
+
+    fn allocate(&mut self, parent: usize) -> usize {
+        match self.free.pop() {
+            Some(node) => {
+                self.nodes[node] = Node::new(Some(parent));
+                node
+            }
+            None => {
+                self.nodes.push(Node::new(Some(parent)));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    // refreshes the jump pointers from `node` up to the root
+    fn update_ancestors(&mut self, node: usize) {
+        let mut current = Some(node);
+        while let Some(node) = current {
+            let [left, right] = self.nodes[node].children;
+            if let Some(child) = left.or(right) {
+                self.nodes[node].min = self.nodes[child].min;
+            }
+            if let Some(child) = right.or(left) {
+                self.nodes[node].max = self.nodes[child].max;
+            }
+            current = self.nodes[node].parent;
+        }
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) -> T {
+        let (prev, next) = (self.nodes[leaf].prev, self.nodes[leaf].next);
+        if let Some(prev) = prev {
+            self.nodes[prev].next = next;
+        }
+        if let Some(next) = next {
+            self.nodes[next].prev = prev;
+        }
+
+        let item = match self.nodes[leaf].item.take() {
+            Some(item) => item,
+            _ => unreachable!(),
+        };
+
+        // drops the nodes left without a leaf
+        let mut node = leaf;
+        let mut depth = T::W;
+        let mut parent = self.nodes[node].parent.unwrap();
+        loop {
+            self.nodes[parent].children[item.bit(depth - 1)] = None;
+            self.free.push(node);
+
+            if parent == ROOT || self.nodes[parent].children != [None, None] {
+                break;
+            }
+            node = parent;
+            depth -= 1;
+            parent = self.nodes[node].parent.unwrap();
+        }
+        self.update_ancestors(parent);
+
+        self.size -= 1;
+        item
+    }
+}
+
+impl<T: Word> Default for BinaryTrie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Word> SSet<T> for BinaryTrie<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, item: T) -> bool {
+        let mut node = ROOT;
+        let mut depth = 0;
+        while depth < T::W {
+            match self.nodes[node].children[item.bit(depth)] {
+                Some(child) => node = child,
+                None => break,
+            }
+            depth += 1;
+        }
+        if depth == T::W {
+            return false;
+        }
+
+        let next = self.search(&item, false);
+        let prev = match next {
+            Some(next) => self.nodes[next].prev,
+            None if self.size > 0 => Some(self.nodes[ROOT].max),
+            None => None,
+        };
+
+        while depth < T::W {
+            let child = self.allocate(node);
+            self.nodes[node].children[item.bit(depth)] = Some(child);
+            node = child;
+            depth += 1;
+        }
+
+        let leaf = &mut self.nodes[node];
+        leaf.min = node;
+        leaf.max = node;
+        leaf.prev = prev;
+        leaf.next = next;
+        leaf.item = Some(item);
+        if let Some(prev) = prev {
+            self.nodes[prev].next = Some(node);
+        }
+        if let Some(next) = next {
+            self.nodes[next].prev = Some(node);
+        }
+        self.update_ancestors(node);
+
+        self.size += 1;
+        true
+    }
+
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let leaf = self.search(item, false)?;
+        if self.item(leaf).borrow() != item {
+            return None;
+        }
+        Some(self.remove_leaf(leaf))
+    }
+
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let leaf = self.search(item, false)?;
+        Some(self.item(leaf))
+    }
+
+    fn successor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let leaf = self.search(item, true)?;
+        Some(self.item(leaf))
+    }
+
+    fn predecessor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.size == 0 {
+            return None;
+        }
+
+        let leaf = match self.search(item, false) {
+            Some(next) => self.nodes[next].prev?,
+            None => self.nodes[ROOT].max,
+        };
+        Some(self.item(leaf))
+    }
+
+    fn first(&self) -> Option<&T> {
+        if self.size == 0 {
+            return None;
+        }
+        Some(self.item(self.nodes[ROOT].min))
+    }
+
+    fn last(&self) -> Option<&T> {
+        if self.size == 0 {
+            return None;
+        }
+        Some(self.item(self.nodes[ROOT].max))
+    }
+
+    fn pop_first(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+        Some(self.remove_leaf(self.nodes[ROOT].min))
+    }
+
+    fn pop_last(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+        Some(self.remove_leaf(self.nodes[ROOT].max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryTrie, ROOT};
+    use crate::SSet;
+
+    #[test]
+    pub fn sset() {
+        crate::tests::sset(BinaryTrie::new());
+    }
+
+    #[test]
+    pub fn ordered() {
+        let mut trie = BinaryTrie::new();
+        for item in [u64::MAX, 0, 1 << 63, 42].iter() {
+            trie.add(*item);
+        }
+        assert_eq!(trie.first(), Some(&0));
+        assert_eq!(trie.last(), Some(&u64::MAX));
+        assert_eq!(trie.find(&43), Some(&(1 << 63)));
+        assert_eq!(trie.successor(&u64::MAX), None);
+        assert_eq!(trie.predecessor(&(1 << 63)), Some(&42));
+
+        assert_eq!(trie.pop_first(), Some(0));
+        assert_eq!(trie.pop_last(), Some(u64::MAX));
+        assert_eq!(trie.pop_last(), Some(1 << 63));
+        assert_eq!(trie.pop_first(), Some(42));
+        assert_eq!(trie.pop_first(), None);
+    }
+
+    #[test]
+    pub fn nodes() {
+        let mut trie = BinaryTrie::new();
+
+        // a path of W nodes below the root, then one leaf for a word differing in its last bit
+        trie.add(0b1010_1010u8);
+        assert_eq!(trie.nodes.len(), 9);
+        trie.add(0b1010_1011);
+        assert_eq!(trie.nodes.len(), 10);
+        trie.add(0b0000_0001);
+        assert_eq!(trie.nodes.len(), 18);
+
+        // the jump pointers of the root follow the least and the greatest leaf
+        assert_eq!(trie.item(trie.nodes[ROOT].min), &0b0000_0001);
+        assert_eq!(trie.item(trie.nodes[ROOT].max), &0b1010_1011);
+        assert!(trie.remove(&0b1010_1011));
+        assert_eq!(trie.item(trie.nodes[ROOT].max), &0b1010_1010);
+        assert_eq!(trie.free.len(), 1);
+        assert!(trie.remove(&0b0000_0001));
+        assert_eq!(trie.item(trie.nodes[ROOT].min), &0b1010_1010);
+        assert_eq!(trie.free.len(), 9);
+
+        // the freed nodes are reused
+        assert!(trie.remove(&0b1010_1010));
+        assert_eq!(trie.nodes[ROOT].children, [None, None]);
+        assert_eq!(trie.free.len(), 17);
+        trie.add(0b1111_1111);
+        assert_eq!(trie.nodes.len(), 18);
+        assert_eq!(trie.free.len(), 9);
+        assert_eq!(trie.find(&0), Some(&0b1111_1111));
+    }
+}
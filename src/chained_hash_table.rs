@@ -0,0 +1,170 @@
+use crate::random::Random;
+use crate::USet;
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const DEFAULT_DIMENSION: u32 = 1;
+const SIZE_DOWN_THRESHOLD: usize = 3;
+
+/// Hashing with chaining: `2^dimension` buckets, the bucket of an item picked by multiplicative
+/// hashing with a random odd multiplier. Operations are O(1) expected, resizing amortized.
+#[derive(Debug)]
+pub struct ChainedHashTable<T> {
+    buckets: Vec<Vec<T>>,
+    size: usize,
+    dimension: u32,
+    multiplier: u64,
+}
+
+impl<T: Hash + Eq> ChainedHashTable<T> {
+    pub fn new() -> ChainedHashTable<T> {
+        ChainedHashTable {
+            buckets: Self::new_buckets(DEFAULT_DIMENSION),
+            size: 0,
+            dimension: DEFAULT_DIMENSION,
+            multiplier: Random::new().next_u64() | 1,
+        }
+    }
+
+    fn new_buckets(dimension: u32) -> Vec<Vec<T>> {
+        (0..1usize << dimension).map(|_| vec![]).collect()
+    }
+
+    fn bucket<Q: Hash + ?Sized>(&self, item: &Q) -> usize {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        (self.multiplier.wrapping_mul(hasher.finish()) >> (u64::BITS - self.dimension)) as usize
+    }
+
+    fn resize(&mut self, dimension: u32) {
+        let buckets = std::mem::replace(&mut self.buckets, Self::new_buckets(dimension));
+        self.dimension = dimension;
+        for item in buckets.into_iter().flatten() {
+            let bucket = self.bucket(&item);
+            self.buckets[bucket].push(item);
+        }
+    }
+}
+
+impl<T: Hash + Eq> Default for ChainedHashTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq> USet<T> for ChainedHashTable<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, item: T) -> bool {
+        if self.find(&item).is_some() {
+            return false;
+        }
+        if self.size + 1 > self.buckets.len() {
+            self.resize(self.dimension + 1);
+        }
+
+        let bucket = self.bucket(&item);
+        self.buckets[bucket].push(item);
+        self.size += 1;
+        true
+    }
+
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let bucket = self.bucket(item);
+        let index = self.buckets[bucket]
+            .iter()
+            .position(|x| x.borrow() == item)?;
+        let item = self.buckets[bucket].swap_remove(index);
+        self.size -= 1;
+
+        if self.size * SIZE_DOWN_THRESHOLD < self.buckets.len()
+            && self.dimension > DEFAULT_DIMENSION
+        {
+            self.resize(self.dimension - 1);
+        }
+        Some(item)
+    }
+
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.buckets[self.bucket(item)]
+            .iter()
+            .find(|x| (*x).borrow() == item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChainedHashTable;
+    use crate::USet;
+
+    #[test]
+    pub fn uset() {
+        let mut table = ChainedHashTable::new();
+        assert_eq!(table.size(), 0);
+
+        assert!(table.add(3));
+        assert!(table.add(1));
+        assert!(table.add(4));
+        assert!(!table.add(1));
+
+        assert_eq!(table.size(), 3);
+        assert_eq!(table.find(&4), Some(&4));
+        assert_eq!(table.find(&5), None);
+
+        assert_eq!(table.take(&1), Some(1));
+        assert_eq!(table.take(&1), None);
+        assert!(table.remove(&3));
+        assert!(!table.remove(&3));
+
+        assert_eq!(table.size(), 1);
+        assert_eq!(table.replace(4), Some(4));
+        assert_eq!(table.size(), 1);
+    }
+
+    #[test]
+    pub fn resize() {
+        let mut table = ChainedHashTable::new();
+        for x in 0..1000u64 {
+            assert!(table.add(x * x));
+        }
+        assert_eq!(table.size(), 1000);
+        assert!(table.buckets.len() >= 1000);
+
+        for x in 0..990u64 {
+            assert_eq!(table.take(&(x * x)), Some(x * x));
+        }
+        assert_eq!(table.size(), 10);
+        assert!(table.buckets.len() <= 3 * 10 * 2);
+        for x in 990..1000u64 {
+            assert_eq!(table.find(&(x * x)), Some(&(x * x)));
+        }
+    }
+
+    #[test]
+    pub fn borrowed() {
+        let mut table = ChainedHashTable::new();
+        table.add(String::from("one"));
+        table.add(String::from("two"));
+
+        assert_eq!(table.find("one").map(String::as_str), Some("one"));
+        assert!(table.remove("two"));
+        assert_eq!(table.find("two"), None);
+
+        assert_eq!(
+            table.get_or_insert_with("three", |item| item.to_string()),
+            "three"
+        );
+        assert_eq!(table.size(), 2);
+    }
+}
@@ -0,0 +1,532 @@
+use crate::owner::Owner;
+use crate::CollectionError;
+use crate::{AddressablePriorityQueue, PriorityQueue};
+use crate::{INVALID_HANDLE_ERROR, INVALID_KEY_ERROR};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+type NodeRef<T> = Rc<RefCell<Node<T>>>;
+type Link<T> = Option<NodeRef<T>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    item: Rc<T>,
+    owner: Owner,
+    parent: Weak<RefCell<Node<T>>>,
+    child: Link<T>,
+    prev: Weak<RefCell<Node<T>>>,
+    next: Link<T>,
+    degree: usize,
+    // lost a child since it became the child of its parent
+    marked: bool,
+}
+
+impl<T> Node<T> {
+    fn new(item: T, owner: Owner) -> NodeRef<T> {
+        Rc::new(RefCell::new(Node {
+            item: Rc::new(item),
+            owner,
+            parent: Weak::new(),
+            child: None,
+            prev: Weak::new(),
+            next: None,
+            degree: 0,
+            marked: false,
+        }))
+    }
+}
+
+/// Refers to an item of a heap, stays valid when the heap is melded into another one.
+pub struct Handle<T>(Weak<RefCell<Node<T>>>);
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle")
+    }
+}
+
+/// Handles of another heap are rejected, those of a heap melded into this one stay valid.
+#[derive(Debug)]
+pub struct FibonacciHeap<T> {
+    roots: Link<T>,
+    last_root: Weak<RefCell<Node<T>>>,
+    min_root: Weak<RefCell<Node<T>>>,
+    // the item of the minimum root, lent out by `peek`
+    min: Option<Rc<T>>,
+    size: usize,
+    owner: Owner,
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub fn new() -> FibonacciHeap<T> {
+        FibonacciHeap {
+            roots: None,
+            last_root: Weak::new(),
+            min_root: Weak::new(),
+            min: None,
+            size: 0,
+            owner: Owner::new(),
+        }
+    }
+
+    /// Moves all the items of `other` into this heap. O(1).
+    pub fn meld(&mut self, mut other: FibonacciHeap<T>) {
+        other.owner.forward_to(&self.owner);
+        let first = match other.roots.take() {
+            Some(first) => first,
+            None => return,
+        };
+
+        match self.last_root.upgrade() {
+            Some(last) => {
+                first.borrow_mut().prev = Rc::downgrade(&last);
+                last.borrow_mut().next = Some(first);
+            }
+            None => self.roots = Some(first),
+        }
+        self.last_root = other.last_root.clone();
+
+        if let Some(min_root) = other.min_root.upgrade() {
+            self.update_min(&min_root);
+        }
+        self.size += other.size;
+    }
+
+    // the node of `handle`, unless it belongs to another heap or was removed
+    fn node(&self, handle: &Handle<T>) -> Option<NodeRef<T>> {
+        let node = handle.0.upgrade()?;
+        if node.borrow().owner.is(&self.owner) {
+            Some(node)
+        } else {
+            None
+        }
+    }
+
+    fn is_min(&self, node: &NodeRef<T>) -> bool {
+        match self.min_root.upgrade() {
+            Some(min_root) => Rc::ptr_eq(node, &min_root),
+            None => false,
+        }
+    }
+
+    fn update_min(&mut self, node: &NodeRef<T>) {
+        let item = Rc::clone(&node.borrow().item);
+        let is_less = match self.min.as_ref() {
+            Some(min) => item < *min,
+            None => true,
+        };
+        if is_less {
+            self.min_root = Rc::downgrade(node);
+            self.min = Some(item);
+        }
+    }
+
+    fn push_root(&mut self, node: NodeRef<T>) {
+        {
+            let mut links = node.borrow_mut();
+            links.parent = Weak::new();
+            links.marked = false;
+        }
+        self.update_min(&node);
+
+        match self.last_root.upgrade() {
+            Some(last) => {
+                node.borrow_mut().prev = Rc::downgrade(&last);
+                self.last_root = Rc::downgrade(&node);
+                last.borrow_mut().next = Some(node);
+            }
+            None => {
+                self.last_root = Rc::downgrade(&node);
+                self.roots = Some(node);
+            }
+        }
+    }
+
+    fn unlink_root(&mut self, node: &NodeRef<T>) {
+        let (prev, next) = {
+            let mut links = node.borrow_mut();
+            let prev = links.prev.upgrade();
+            links.prev = Weak::new();
+            (prev, links.next.take())
+        };
+
+        match next.as_ref() {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map_or(Weak::new(), Rc::downgrade),
+            None => self.last_root = prev.as_ref().map_or(Weak::new(), Rc::downgrade),
+        }
+        match prev {
+            Some(prev) => prev.borrow_mut().next = next,
+            None => self.roots = next,
+        }
+    }
+
+    // moves the subtree of a non-root `node` to the root list
+    fn cut(&mut self, node: NodeRef<T>, parent: &NodeRef<T>) {
+        let (prev, next) = {
+            let mut links = node.borrow_mut();
+            let prev = links.prev.upgrade();
+            links.prev = Weak::new();
+            (prev, links.next.take())
+        };
+
+        if let Some(next) = next.as_ref() {
+            next.borrow_mut().prev = prev.as_ref().map_or(Weak::new(), Rc::downgrade);
+        }
+        {
+            let mut parent_links = parent.borrow_mut();
+            parent_links.degree -= 1;
+            match prev {
+                Some(prev) => prev.borrow_mut().next = next,
+                None => parent_links.child = next,
+            }
+        }
+
+        self.push_root(node);
+    }
+
+    fn cascading_cut(&mut self, mut node: NodeRef<T>) {
+        loop {
+            let parent = match node.borrow().parent.upgrade() {
+                Some(parent) => parent,
+                None => return,
+            };
+
+            if !node.borrow().marked {
+                node.borrow_mut().marked = true;
+                return;
+            }
+
+            self.cut(node, &parent);
+            node = parent;
+        }
+    }
+
+    // cuts `node` from its parent if it has one
+    fn cut_to_root(&mut self, node: &NodeRef<T>) {
+        let parent = node.borrow().parent.upgrade();
+        if let Some(parent) = parent {
+            self.cut(Rc::clone(node), &parent);
+            self.cascading_cut(parent);
+        }
+    }
+
+    fn promote_children(&mut self, node: &NodeRef<T>) {
+        let mut child = {
+            let mut links = node.borrow_mut();
+            links.degree = 0;
+            links.child.take()
+        };
+
+        while let Some(current) = child {
+            {
+                let mut links = current.borrow_mut();
+                links.prev = Weak::new();
+                child = links.next.take();
+            }
+            self.push_root(current);
+        }
+    }
+
+    // links roots of equal degree until all degrees differ, and finds the new minimum
+    fn consolidate(&mut self) {
+        let mut table: Vec<Link<T>> = vec![];
+
+        let mut root = self.roots.take();
+        self.last_root = Weak::new();
+        self.min_root = Weak::new();
+        self.min = None;
+
+        while let Some(current) = root {
+            {
+                let mut links = current.borrow_mut();
+                links.prev = Weak::new();
+                root = links.next.take();
+            }
+
+            let mut tree = current;
+            loop {
+                let degree = tree.borrow().degree;
+                if table.len() <= degree {
+                    table.resize_with(degree + 1, || None);
+                }
+                match table[degree].take() {
+                    Some(other) => tree = link(tree, other),
+                    None => {
+                        table[degree] = Some(tree);
+                        break;
+                    }
+                }
+            }
+        }
+
+        for tree in table.into_iter().flatten() {
+            self.push_root(tree);
+        }
+    }
+}
+
+// makes the root with the larger item the first child of the other one
+fn link<T: Ord>(a: NodeRef<T>, b: NodeRef<T>) -> NodeRef<T> {
+    let (parent, child) = if b.borrow().item < a.borrow().item {
+        (b, a)
+    } else {
+        (a, b)
+    };
+
+    {
+        let mut parent_links = parent.borrow_mut();
+        let mut child_links = child.borrow_mut();
+        if let Some(first) = parent_links.child.take() {
+            first.borrow_mut().prev = Rc::downgrade(&child);
+            child_links.next = Some(first);
+        }
+        child_links.parent = Rc::downgrade(&parent);
+        child_links.marked = false;
+        parent_links.degree += 1;
+    }
+    parent.borrow_mut().child = Some(child);
+
+    parent
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for FibonacciHeap<T> {
+    // root and child lists can be long, so the nodes are not dropped recursively
+    fn drop(&mut self) {
+        let mut nodes: Vec<NodeRef<T>> = self.roots.take().into_iter().collect();
+        while let Some(node) = nodes.pop() {
+            let mut links = node.borrow_mut();
+            nodes.extend(links.child.take());
+            nodes.extend(links.next.take());
+        }
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for FibonacciHeap<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    /// O(1).
+    fn add(&mut self, x: T) {
+        self.insert(x);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.min.as_deref()
+    }
+
+    /// O(log n) amortized.
+    fn remove_min(&mut self) -> Option<T> {
+        let handle = Handle(self.min_root.clone());
+        self.remove(&handle)
+    }
+}
+
+impl<T: Ord> AddressablePriorityQueue<T> for FibonacciHeap<T> {
+    type Handle = Handle<T>;
+
+    /// O(1).
+    fn insert(&mut self, x: T) -> Handle<T> {
+        let node = Node::new(x, self.owner.clone());
+        let handle = Handle(Rc::downgrade(&node));
+
+        self.push_root(node);
+        self.size += 1;
+
+        handle
+    }
+
+    fn contains(&self, handle: &Handle<T>) -> bool {
+        self.node(handle).is_some()
+    }
+
+    /// O(1) amortized.
+    fn decrease_key(&mut self, handle: &Handle<T>, x: T) -> Result<(), CollectionError> {
+        let node = self.node(handle).ok_or(INVALID_HANDLE_ERROR)?;
+        if x > *node.borrow().item {
+            return Err(INVALID_KEY_ERROR);
+        }
+
+        node.borrow_mut().item = Rc::new(x);
+
+        let parent = node.borrow().parent.upgrade();
+        match parent {
+            Some(parent) if node.borrow().item < parent.borrow().item => {
+                self.cut(Rc::clone(&node), &parent);
+                self.cascading_cut(parent);
+            }
+            Some(_) => (),
+            None if self.is_min(&node) => self.min = Some(Rc::clone(&node.borrow().item)),
+            None => self.update_min(&node),
+        }
+        Ok(())
+    }
+
+    /// O(log n) amortized.
+    fn increase_key(&mut self, handle: &Handle<T>, x: T) -> Result<(), CollectionError> {
+        let node = self.node(handle).ok_or(INVALID_HANDLE_ERROR)?;
+        if x < *node.borrow().item {
+            return Err(INVALID_KEY_ERROR);
+        }
+
+        self.cut_to_root(&node);
+        node.borrow_mut().item = Rc::new(x);
+        self.promote_children(&node);
+
+        if self.is_min(&node) {
+            self.consolidate();
+        }
+        Ok(())
+    }
+
+    /// O(log n) amortized.
+    fn remove(&mut self, handle: &Handle<T>) -> Option<T> {
+        let node = self.node(handle)?;
+
+        self.cut_to_root(&node);
+        self.promote_children(&node);
+        self.unlink_root(&node);
+        self.size -= 1;
+
+        self.min_root = Weak::new();
+        self.min = None;
+        self.consolidate();
+
+        let item = match Rc::try_unwrap(node) {
+            Ok(node) => node.into_inner().item,
+            _ => unreachable!(),
+        };
+        match Rc::try_unwrap(item) {
+            Ok(item) => Some(item),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FibonacciHeap, Handle, NodeRef};
+    use crate::{AddressablePriorityQueue, PriorityQueue};
+    use std::rc::Rc;
+
+    #[test]
+    pub fn priority_queue() {
+        crate::tests::priority_queue(FibonacciHeap::new());
+    }
+
+    #[test]
+    pub fn addressable() {
+        crate::tests::addressable(FibonacciHeap::new());
+    }
+
+    #[test]
+    pub fn meld() {
+        crate::tests::meld(
+            FibonacciHeap::new(),
+            FibonacciHeap::new(),
+            FibonacciHeap::meld,
+        );
+    }
+
+    #[test]
+    pub fn foreign_handle() {
+        crate::tests::foreign_handle(FibonacciHeap::new(), FibonacciHeap::new());
+    }
+
+    fn siblings(first: Option<NodeRef<u32>>) -> Vec<NodeRef<u32>> {
+        let mut nodes = vec![];
+        let mut current = first;
+        while let Some(node) = current {
+            current = node.borrow().next.clone();
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    // checks the heap order, links and degrees below `node`, returns the number of nodes
+    fn check(node: &NodeRef<u32>) -> usize {
+        let children = siblings(node.borrow().child.clone());
+        assert_eq!(node.borrow().degree, children.len());
+
+        let mut size = 1;
+        for (i, child) in children.iter().enumerate() {
+            assert!(child.borrow().item >= node.borrow().item);
+            assert!(Rc::ptr_eq(&child.borrow().parent.upgrade().unwrap(), node));
+            match child.borrow().prev.upgrade() {
+                Some(prev) => assert!(Rc::ptr_eq(&prev, &children[i - 1])),
+                None => assert_eq!(i, 0),
+            }
+            size += check(child);
+        }
+        size
+    }
+
+    fn roots(heap: &FibonacciHeap<u32>) -> Vec<NodeRef<u32>> {
+        let roots = siblings(heap.roots.clone());
+        let size: usize = roots.iter().map(check).sum();
+        assert_eq!(size, heap.size());
+        roots
+    }
+
+    #[test]
+    pub fn consolidate() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<_> = (0..16).map(|item| heap.insert(item)).collect();
+        assert_eq!(roots(&heap).len(), 16);
+
+        // 15 items are linked into trees of 8, 4, 2 and 1 nodes
+        assert_eq!(heap.remove_min(), Some(0));
+        let mut degrees: Vec<usize> = roots(&heap)
+            .iter()
+            .map(|root| root.borrow().degree)
+            .collect();
+        degrees.sort();
+        assert_eq!(degrees, vec![0, 1, 2, 3]);
+
+        // a marked node losing a second child is cut as well
+        let node = |handle: &Handle<u32>| handle.0.upgrade().unwrap();
+        let parent = |node: &NodeRef<u32>| node.borrow().parent.upgrade();
+        let children: Vec<_> = handles[1..]
+            .iter()
+            .filter(|handle| match parent(&node(handle)) {
+                Some(p) => p.borrow().degree == 2 && parent(&p).is_some(),
+                None => false,
+            })
+            .cloned()
+            .collect();
+        assert_eq!(children.len(), 2);
+        let p = Rc::downgrade(&parent(&node(&children[0])).unwrap());
+        let grandparent = Rc::downgrade(&parent(&p.upgrade().unwrap()).unwrap());
+
+        assert_eq!(heap.decrease_key(&children[0], 0), Ok(()));
+        assert!(p.upgrade().unwrap().borrow().marked);
+        assert_eq!(roots(&heap).len(), 5);
+
+        assert_eq!(heap.decrease_key(&children[1], 0), Ok(()));
+        assert!(parent(&p.upgrade().unwrap()).is_none());
+        assert!(!p.upgrade().unwrap().borrow().marked);
+        assert!(!grandparent.upgrade().unwrap().borrow().marked);
+        assert_eq!(roots(&heap).len(), 7);
+
+        let mut items = vec![];
+        while let Some(item) = heap.remove_min() {
+            items.push(item);
+            roots(&heap);
+        }
+        assert_eq!(items.len(), 15);
+        assert_eq!(&items[..2], &[0, 0]);
+    }
+}
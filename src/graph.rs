@@ -0,0 +1,49 @@
+//! Directed graphs over the vertices `0..n`.
+
+use crate::array_stack::ArrayStack;
+use crate::CollectionError;
+
+mod adjacency_lists;
+mod adjacency_matrix;
+mod components;
+mod dimacs;
+mod dot;
+mod edge_list;
+mod flow;
+mod matching;
+mod parse;
+mod shortest_path;
+mod spanning_tree;
+mod topological;
+mod traversal;
+mod weighted;
+
+pub use adjacency_lists::AdjacencyLists;
+pub use adjacency_matrix::AdjacencyMatrix;
+pub use components::{kosaraju_scc, tarjan_scc};
+pub use dimacs::{read_dimacs, read_weighted_dimacs, write_dimacs, write_weighted_dimacs};
+pub use dot::{read_dot, read_weighted_dot, write_dot, write_weighted_dot};
+pub use edge_list::{
+    read_edge_list, read_weighted_edge_list, write_edge_list, write_weighted_edge_list,
+};
+pub use flow::{dinic, edmonds_karp, MaxFlow};
+pub use matching::{hopcroft_karp, Matching};
+pub use parse::ParseError;
+pub use shortest_path::{a_star, bellman_ford, dijkstra, zero_one_bfs, ShortestPaths};
+pub use spanning_tree::{kruskal, prim};
+pub use topological::{find_cycle, has_cycle, topological_sort, topological_sort_dfs};
+pub use traversal::{bfs, bfs_with, dfs, dfs_with, Bfs, Dfs, Visit};
+pub use weighted::{Weight, WeightedAdjacencyLists, WeightedGraph};
+
+pub trait Graph {
+    /// The number of vertices.
+    fn n(&self) -> usize;
+    /// Adds the edge `(i, j)` unless it exists, fails when a vertex is out of bound.
+    fn add_edge(&mut self, i: usize, j: usize) -> Result<(), CollectionError>;
+    fn remove_edge(&mut self, i: usize, j: usize) -> bool;
+    fn has_edge(&self, i: usize, j: usize) -> bool;
+    /// The vertices `j` of the edges `(i, j)`.
+    fn out_edges(&self, i: usize) -> ArrayStack<usize>;
+    /// The vertices `j` of the edges `(j, i)`.
+    fn in_edges(&self, i: usize) -> ArrayStack<usize>;
+}
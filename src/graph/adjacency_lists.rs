@@ -0,0 +1,107 @@
+use super::Graph;
+use crate::array_stack::ArrayStack;
+use crate::CollectionError;
+use crate::OUT_OF_BOUND_ERROR;
+use crate::{List, Stack};
+
+/// O(deg(i)) edge operations and `out_edges`, O(n + m) `in_edges`, O(n + m) space.
+#[derive(Debug)]
+pub struct AdjacencyLists {
+    adjacency: Vec<ArrayStack<usize>>,
+}
+
+impl AdjacencyLists {
+    pub fn new(n: usize) -> AdjacencyLists {
+        AdjacencyLists {
+            adjacency: (0..n).map(|_| ArrayStack::new()).collect(),
+        }
+    }
+
+    fn position(&self, i: usize, j: usize) -> Option<usize> {
+        let edges = self.adjacency.get(i)?;
+        edges.iter().position(|k| *k == j)
+    }
+}
+
+impl Graph for AdjacencyLists {
+    fn n(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn add_edge(&mut self, i: usize, j: usize) -> Result<(), CollectionError> {
+        if i >= self.n() || j >= self.n() {
+            return Err(OUT_OF_BOUND_ERROR);
+        }
+
+        if self.position(i, j).is_none() {
+            self.adjacency[i].push(j);
+        }
+        Ok(())
+    }
+
+    fn remove_edge(&mut self, i: usize, j: usize) -> bool {
+        match self.position(i, j) {
+            Some(position) => self.adjacency[i].remove(position).is_some(),
+            None => false,
+        }
+    }
+
+    fn has_edge(&self, i: usize, j: usize) -> bool {
+        self.position(i, j).is_some()
+    }
+
+    fn out_edges(&self, i: usize) -> ArrayStack<usize> {
+        let mut edges = ArrayStack::new();
+        if let Some(out_edges) = self.adjacency.get(i) {
+            for j in out_edges.iter() {
+                edges.push(*j);
+            }
+        }
+        edges
+    }
+
+    fn in_edges(&self, i: usize) -> ArrayStack<usize> {
+        let mut edges = ArrayStack::new();
+        for j in (0..self.n()).filter(|j| self.has_edge(*j, i)) {
+            edges.push(j);
+        }
+        edges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AdjacencyLists;
+    use crate::graph::Graph;
+    use crate::OUT_OF_BOUND_ERROR;
+
+    #[test]
+    pub fn graph() {
+        let mut graph = AdjacencyLists::new(4);
+        assert_eq!(graph.n(), 4);
+
+        assert_eq!(graph.add_edge(0, 2), Ok(()));
+        assert_eq!(graph.add_edge(0, 1), Ok(()));
+        assert_eq!(graph.add_edge(2, 0), Ok(()));
+        assert_eq!(graph.add_edge(3, 0), Ok(()));
+        assert_eq!(graph.add_edge(0, 1), Ok(()));
+        assert_eq!(graph.add_edge(4, 0), Err(OUT_OF_BOUND_ERROR));
+
+        assert!(graph.has_edge(0, 1));
+        assert!(!graph.has_edge(1, 0));
+        assert!(!graph.has_edge(4, 0));
+
+        // in the order the edges were added
+        let out_edges: Vec<usize> = graph.out_edges(0).iter().copied().collect();
+        assert_eq!(out_edges, vec![2, 1]);
+        let in_edges: Vec<usize> = graph.in_edges(0).iter().copied().collect();
+        assert_eq!(in_edges, vec![2, 3]);
+
+        assert!(graph.remove_edge(0, 2));
+        assert!(!graph.remove_edge(0, 2));
+        assert!(!graph.remove_edge(4, 0));
+        assert_eq!(graph.add_edge(0, 2), Ok(()));
+        let out_edges: Vec<usize> = graph.out_edges(0).iter().copied().collect();
+        assert_eq!(out_edges, vec![1, 2]);
+    }
+}
@@ -0,0 +1,106 @@
+use super::Graph;
+use crate::array_stack::ArrayStack;
+use crate::CollectionError;
+use crate::Stack;
+use crate::OUT_OF_BOUND_ERROR;
+
+/// O(1) edge operations, O(n) `out_edges` and `in_edges`, O(n^2) space.
+#[derive(Debug)]
+pub struct AdjacencyMatrix {
+    n: usize,
+    edges: Vec<bool>,
+}
+
+impl AdjacencyMatrix {
+    pub fn new(n: usize) -> AdjacencyMatrix {
+        AdjacencyMatrix {
+            n,
+            edges: vec![false; n * n],
+        }
+    }
+
+    fn index(&self, i: usize, j: usize) -> Option<usize> {
+        if i >= self.n || j >= self.n {
+            return None;
+        }
+
+        Some(i * self.n + j)
+    }
+}
+
+impl Graph for AdjacencyMatrix {
+    fn n(&self) -> usize {
+        self.n
+    }
+
+    fn add_edge(&mut self, i: usize, j: usize) -> Result<(), CollectionError> {
+        let index = self.index(i, j).ok_or(OUT_OF_BOUND_ERROR)?;
+        self.edges[index] = true;
+        Ok(())
+    }
+
+    fn remove_edge(&mut self, i: usize, j: usize) -> bool {
+        match self.index(i, j) {
+            Some(index) => std::mem::replace(&mut self.edges[index], false),
+            None => false,
+        }
+    }
+
+    fn has_edge(&self, i: usize, j: usize) -> bool {
+        match self.index(i, j) {
+            Some(index) => self.edges[index],
+            None => false,
+        }
+    }
+
+    fn out_edges(&self, i: usize) -> ArrayStack<usize> {
+        let mut edges = ArrayStack::new();
+        for j in (0..self.n).filter(|j| self.has_edge(i, *j)) {
+            edges.push(j);
+        }
+        edges
+    }
+
+    fn in_edges(&self, i: usize) -> ArrayStack<usize> {
+        let mut edges = ArrayStack::new();
+        for j in (0..self.n).filter(|j| self.has_edge(*j, i)) {
+            edges.push(j);
+        }
+        edges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AdjacencyMatrix;
+    use crate::graph::Graph;
+    use crate::OUT_OF_BOUND_ERROR;
+
+    #[test]
+    pub fn graph() {
+        let mut graph = AdjacencyMatrix::new(4);
+        assert_eq!(graph.n(), 4);
+
+        assert_eq!(graph.add_edge(0, 1), Ok(()));
+        assert_eq!(graph.add_edge(0, 2), Ok(()));
+        assert_eq!(graph.add_edge(2, 0), Ok(()));
+        assert_eq!(graph.add_edge(3, 0), Ok(()));
+        assert_eq!(graph.add_edge(0, 1), Ok(()));
+        assert_eq!(graph.add_edge(0, 4), Err(OUT_OF_BOUND_ERROR));
+
+        assert!(graph.has_edge(0, 1));
+        assert!(!graph.has_edge(1, 0));
+        assert!(!graph.has_edge(4, 0));
+
+        let out_edges: Vec<usize> = graph.out_edges(0).iter().copied().collect();
+        assert_eq!(out_edges, vec![1, 2]);
+        let in_edges: Vec<usize> = graph.in_edges(0).iter().copied().collect();
+        assert_eq!(in_edges, vec![2, 3]);
+
+        assert!(graph.remove_edge(0, 1));
+        assert!(!graph.remove_edge(0, 1));
+        assert!(!graph.remove_edge(0, 4));
+        assert!(!graph.has_edge(0, 1));
+        assert_eq!(graph.out_edges(0).iter().count(), 1);
+    }
+}
@@ -0,0 +1,179 @@
+use super::Graph;
+use crate::array_stack::ArrayStack;
+use crate::{List, Stack};
+
+/// Tarjan's algorithm in O(n + m). The components come in reverse topological order, no edge
+/// leads from a component to a later one, and each lists its vertices in ascending order.
+pub fn tarjan_scc<G: Graph + ?Sized>(graph: &G) -> Vec<Vec<usize>> {
+    let mut indices = vec![None; graph.n()];
+    let mut low_links = vec![0; graph.n()];
+    let mut is_on_stack = vec![false; graph.n()];
+    // the visited vertices whose component is not complete yet
+    let mut stack = ArrayStack::new();
+    // the vertices of the depth-first path with their out edges and the index of the next one
+    let mut path = ArrayStack::new();
+    let mut next_index = 0;
+    let mut components = vec![];
+
+    for root in 0..graph.n() {
+        if indices[root].is_some() {
+            continue;
+        }
+        indices[root] = Some(next_index);
+        low_links[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        is_on_stack[root] = true;
+        path.push((root, graph.out_edges(root), 0));
+
+        while let Some((vertex, edges, index)) = path.pop() {
+            if let Some(neighbour) = edges.get(index).copied() {
+                path.push((vertex, edges, index + 1));
+
+                match indices[neighbour] {
+                    None => {
+                        indices[neighbour] = Some(next_index);
+                        low_links[neighbour] = next_index;
+                        next_index += 1;
+                        stack.push(neighbour);
+                        is_on_stack[neighbour] = true;
+                        path.push((neighbour, graph.out_edges(neighbour), 0));
+                    }
+                    Some(neighbour_index) if is_on_stack[neighbour] => {
+                        low_links[vertex] = std::cmp::min(low_links[vertex], neighbour_index);
+                    }
+                    Some(_) => (),
+                }
+                continue;
+            }
+
+            if indices[vertex] == Some(low_links[vertex]) {
+                let mut component = vec![];
+                while let Some(member) = stack.pop() {
+                    is_on_stack[member] = false;
+                    component.push(member);
+                    if member == vertex {
+                        break;
+                    }
+                }
+                component.sort();
+                components.push(component);
+            }
+
+            if path.size() > 0 {
+                let (parent, _, _) = path.get(path.size() - 1).unwrap();
+                low_links[*parent] = std::cmp::min(low_links[*parent], low_links[vertex]);
+            }
+        }
+    }
+
+    components
+}
+
+/// Kosaraju's algorithm in O(n + m). The components come in topological order, no edge leads
+/// from a component to an earlier one, and each lists its vertices in ascending order.
+pub fn kosaraju_scc<G: Graph + ?Sized>(graph: &G) -> Vec<Vec<usize>> {
+    let mut reversed = vec![vec![]; graph.n()];
+    for vertex in 0..graph.n() {
+        for neighbour in graph.out_edges(vertex) {
+            reversed[neighbour].push(vertex);
+        }
+    }
+
+    // the source components finish last
+    let mut is_visited = vec![false; graph.n()];
+    let mut order = Vec::with_capacity(graph.n());
+    let mut path = ArrayStack::new();
+    for root in 0..graph.n() {
+        if is_visited[root] {
+            continue;
+        }
+        is_visited[root] = true;
+        path.push((root, graph.out_edges(root), 0));
+
+        while let Some((vertex, edges, index)) = path.pop() {
+            match edges.get(index).copied() {
+                Some(neighbour) => {
+                    path.push((vertex, edges, index + 1));
+                    if !is_visited[neighbour] {
+                        is_visited[neighbour] = true;
+                        path.push((neighbour, graph.out_edges(neighbour), 0));
+                    }
+                }
+                None => order.push(vertex),
+            }
+        }
+    }
+
+    // a search on the reversed edges from a source component cannot leave it
+    let mut is_assigned = vec![false; graph.n()];
+    let mut components = vec![];
+    for root in order.into_iter().rev() {
+        if is_assigned[root] {
+            continue;
+        }
+        is_assigned[root] = true;
+
+        let mut component = vec![];
+        let mut stack = ArrayStack::new();
+        stack.push(root);
+        while let Some(vertex) = stack.pop() {
+            component.push(vertex);
+            for neighbour in reversed[vertex].iter() {
+                if !is_assigned[*neighbour] {
+                    is_assigned[*neighbour] = true;
+                    stack.push(*neighbour);
+                }
+            }
+        }
+        component.sort();
+        components.push(component);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kosaraju_scc, tarjan_scc};
+    use crate::graph::{AdjacencyLists, AdjacencyMatrix, Graph};
+
+    #[test]
+    pub fn strongly_connected() {
+        //   0 -> 1 -> 2 -> 3 <-> 4
+        //   ^         |    |
+        //   +---------+    v
+        //                  5    6
+        let mut graph = AdjacencyMatrix::new(7);
+        for (i, j) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (3, 5)].iter() {
+            graph.add_edge(*i, *j).unwrap();
+        }
+
+        let components = vec![vec![6], vec![0, 1, 2], vec![3, 4], vec![5]];
+        assert_eq!(kosaraju_scc(&graph), components);
+
+        let mut components = tarjan_scc(&graph);
+        let last = components.pop();
+        assert_eq!(last, Some(vec![6]));
+        assert_eq!(components, vec![vec![5], vec![3, 4], vec![0, 1, 2]]);
+    }
+
+    #[test]
+    pub fn long_cycle() {
+        // deep enough to overflow a recursive search
+        let n = 100_000;
+        let mut graph = AdjacencyLists::new(n);
+        for i in 0..n {
+            graph.add_edge(i, (i + 1) % n).unwrap();
+        }
+
+        let components = tarjan_scc(&graph);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), n);
+        assert_eq!(kosaraju_scc(&graph), components);
+
+        graph.remove_edge(n - 1, 0);
+        assert_eq!(tarjan_scc(&graph).len(), n);
+        assert_eq!(kosaraju_scc(&graph).len(), n);
+    }
+}
@@ -0,0 +1,240 @@
+//! The DIMACS formats, with a problem line `p <problem> n m` before `m` lines `a i j` of arcs or
+//! `e i j` of undirected edges, read as the arcs `(i, j)` and `(j, i)`. Weighted graphs have a
+//! weight after `j`. Vertices are counted from 1 and lines starting with `c` are comments.
+
+use super::parse::{lines, Edges, Line, ParseError};
+use super::{AdjacencyLists, Graph, Weight, WeightedAdjacencyLists, WeightedGraph};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Fails on a problem line of more than `max_n` vertices, and on parallel arcs.
+pub fn read_dimacs(text: &str, max_n: usize) -> Result<AdjacencyLists, ParseError> {
+    let (n, edges) = read(text, 3, max_n, |_| Ok(()))?;
+
+    let mut graph = AdjacencyLists::new(n);
+    for (i, j, _) in edges {
+        graph.add_edge(i, j).unwrap();
+    }
+    Ok(graph)
+}
+
+/// Fails on a problem line of more than `max_n` vertices, and on parallel arcs.
+pub fn read_weighted_dimacs<W>(
+    text: &str,
+    max_n: usize,
+) -> Result<WeightedAdjacencyLists<W>, ParseError>
+where
+    W: Weight + FromStr,
+{
+    let (n, edges) = read(text, 4, max_n, |line| line.field(3))?;
+
+    let mut graph = WeightedAdjacencyLists::new(n);
+    for (i, j, weight) in edges {
+        graph.add_edge(i, j, weight).unwrap();
+    }
+    Ok(graph)
+}
+
+/// Writes every edge as an arc, under the problem line `p <problem> n m`.
+pub fn write_dimacs<G: Graph + ?Sized>(graph: &G, problem: &str) -> String {
+    let mut arcs = String::new();
+    let mut m = 0;
+    for i in 0..graph.n() {
+        for j in graph.out_edges(i) {
+            arcs.push_str(&format!("a {} {}\n", i + 1, j + 1));
+            m += 1;
+        }
+    }
+    format!("p {} {} {}\n{}", problem, graph.n(), m, arcs)
+}
+
+/// Writes every edge as an arc, under the problem line `p <problem> n m`.
+pub fn write_weighted_dimacs<W, G>(graph: &G, problem: &str) -> String
+where
+    W: Weight + Display,
+    G: WeightedGraph<W> + ?Sized,
+{
+    let mut arcs = String::new();
+    let mut m = 0;
+    for i in 0..graph.n() {
+        for (j, weight) in graph.out_edges(i) {
+            arcs.push_str(&format!("a {} {} {}\n", i + 1, j + 1, weight));
+            m += 1;
+        }
+    }
+    format!("p {} {} {}\n{}", problem, graph.n(), m, arcs)
+}
+
+// the vertex count and the edges of arc and edge lines with `len` fields
+fn read<W, F>(
+    text: &str,
+    len: usize,
+    max_n: usize,
+    mut weight: F,
+) -> Result<(usize, Edges<W>), ParseError>
+where
+    W: Clone,
+    F: FnMut(&Line) -> Result<W, ParseError>,
+{
+    // the line with the vertex and edge counts
+    let mut problem: Option<(Line, usize, usize)> = None;
+    let mut edges = vec![];
+    // the graphs hold a single arc from a vertex to another
+    let mut arcs = HashSet::new();
+    let mut count = 0;
+
+    for line in lines(text, "c") {
+        match line.text(0) {
+            Some("p") => {
+                if problem.is_some() {
+                    return Err(line.error(0, "duplicate problem line"));
+                }
+                line.expect_len(4)?;
+                let (n, m) = (line.field(2)?, line.field(3)?);
+                if n > max_n {
+                    return Err(line.error(2, "too many vertices"));
+                }
+                problem = Some((line, n, m));
+            }
+            Some(kind @ "a") | Some(kind @ "e") => {
+                let n = match problem {
+                    Some((_, n, _)) => n,
+                    None => return Err(line.error(0, "missing problem line")),
+                };
+                line.expect_len(len)?;
+                let (i, j) = (vertex(&line, 1, n)?, vertex(&line, 2, n)?);
+                let weight = weight(&line)?;
+                let line_arcs = if kind == "e" && i != j {
+                    vec![(j, i), (i, j)]
+                } else {
+                    vec![(i, j)]
+                };
+                if line_arcs.iter().any(|arc| arcs.contains(arc)) {
+                    return Err(line.error(0, "parallel edge"));
+                }
+
+                for (i, j) in line_arcs {
+                    arcs.insert((i, j));
+                    edges.push((i, j, weight.clone()));
+                }
+                count += 1;
+            }
+            // the source and sink designators of max flow problems
+            Some("n") => (),
+            _ => return Err(line.error(0, "unknown line")),
+        }
+    }
+
+    match problem {
+        Some((line, n, m)) => {
+            if count != m {
+                return Err(line.error(3, "edge count mismatch"));
+            }
+            Ok((n, edges))
+        }
+        None => Err(ParseError::new(1, 1, "missing problem line")),
+    }
+}
+
+fn vertex(line: &Line, index: usize, n: usize) -> Result<usize, ParseError> {
+    let vertex: usize = line.field(index)?;
+    if vertex == 0 || vertex > n {
+        return Err(line.error(index, "vertex out of bound"));
+    }
+    Ok(vertex - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_dimacs, read_weighted_dimacs, write_dimacs, write_weighted_dimacs};
+    use crate::graph::parse::ParseError;
+    use crate::graph::{AdjacencyLists, Graph, WeightedGraph};
+
+    #[test]
+    pub fn round_trip() {
+        let mut graph = AdjacencyLists::new(4);
+        graph.add_edge(0, 3).unwrap();
+        graph.add_edge(2, 1).unwrap();
+
+        let text = write_dimacs(&graph, "edge");
+        assert_eq!(text, "p edge 4 2\na 1 4\na 3 2\n");
+        assert_eq!(
+            write_dimacs(&read_dimacs(&text, 100).unwrap(), "edge"),
+            text
+        );
+
+        let text = "c a shortest path problem\np sp 3 3\na 1 2 5\na 2 3 -1\nc\na 3 1 2\n";
+        let graph = read_weighted_dimacs::<i64>(text, 100).unwrap();
+        assert_eq!(graph.n(), 3);
+        assert_eq!(graph.weight(1, 2), Some(&-1));
+        let text = write_weighted_dimacs(&graph, "sp");
+        assert_eq!(text, "p sp 3 3\na 1 2 5\na 2 3 -1\na 3 1 2\n");
+        assert_eq!(
+            write_weighted_dimacs(&read_weighted_dimacs::<i64>(&text, 100).unwrap(), "sp"),
+            text
+        );
+
+        let graph = read_dimacs("p col 3 1\ne 1 3\n", 100).unwrap();
+        assert!(graph.has_edge(0, 2));
+        assert!(graph.has_edge(2, 0));
+    }
+
+    #[test]
+    pub fn errors() {
+        assert_eq!(
+            read_dimacs("a 1 2\n", 100).err(),
+            Some(ParseError::new(1, 1, "missing problem line"))
+        );
+        assert_eq!(
+            read_dimacs("p edge 2 1\na 1 3\n", 100).err(),
+            Some(ParseError::new(2, 5, "vertex out of bound"))
+        );
+        assert_eq!(
+            read_dimacs("p edge 2 2\na 1 2\n", 100).err(),
+            Some(ParseError::new(1, 10, "edge count mismatch"))
+        );
+        assert_eq!(
+            read_dimacs("p edge 2 1\nx 1 2\n", 100).err(),
+            Some(ParseError::new(2, 1, "unknown line"))
+        );
+        assert_eq!(
+            read_weighted_dimacs::<u32>("p sp 2 1\na 1 2\n", 100).err(),
+            Some(ParseError::new(2, 6, "missing field"))
+        );
+        assert_eq!(
+            read_dimacs("", 100).err(),
+            Some(ParseError::new(1, 1, "missing problem line"))
+        );
+        assert_eq!(
+            read_dimacs("p edge 1000000000000000 0\n", 100).err(),
+            Some(ParseError::new(1, 8, "too many vertices"))
+        );
+        assert_eq!(read_dimacs("p edge 100 0\n", 100).unwrap().n(), 100);
+    }
+
+    #[test]
+    pub fn parallel_edges() {
+        assert_eq!(
+            read_weighted_dimacs::<u32>("p max 2 2\na 1 2 3\na 1 2 4\n", 100).err(),
+            Some(ParseError::new(3, 1, "parallel edge"))
+        );
+        assert_eq!(
+            read_dimacs("p col 2 2\ne 1 2\ne 2 1\n", 100).err(),
+            Some(ParseError::new(3, 1, "parallel edge"))
+        );
+        assert_eq!(
+            read_dimacs("p sp 2 2\ne 1 2\na 2 1\n", 100).err(),
+            Some(ParseError::new(3, 1, "parallel edge"))
+        );
+
+        // antiparallel arcs and loops are kept
+        let text = "p max 2 3\na 1 2 3\na 2 1 4\na 2 2 1\n";
+        let graph = read_weighted_dimacs::<u32>(text, 100).unwrap();
+        assert_eq!(graph.weight(0, 1), Some(&3));
+        assert_eq!(graph.weight(1, 0), Some(&4));
+        assert_eq!(write_weighted_dimacs(&graph, "max"), text);
+        let graph = read_dimacs("p col 2 1\ne 2 2\n", 100).unwrap();
+        assert_eq!(write_dimacs(&graph, "col"), "p col 2 1\na 2 2\n");
+    }
+}
@@ -0,0 +1,478 @@
+//! Graphviz DOT, with the vertices as numeric node ids and the weights in a `weight` attribute.
+//! The reader takes a `digraph`, or a `graph` whose edges are read in both directions, of node,
+//! edge and attribute statements. Subgraphs and ports are not supported, and attributes other
+//! than the edge weights are ignored.
+
+use super::parse::{Edges, ParseError};
+use super::{AdjacencyLists, Graph, Weight, WeightedAdjacencyLists, WeightedGraph};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Fails on a vertex of `max_n` or more, which would not fit in a graph of `max_n` vertices.
+pub fn read_dot(text: &str, max_n: usize) -> Result<AdjacencyLists, ParseError> {
+    let (n, edges) = read(text, max_n, |_, _| Ok(()))?;
+
+    let mut graph = AdjacencyLists::new(n);
+    for (i, j, _) in edges {
+        graph.add_edge(i, j).unwrap();
+    }
+    Ok(graph)
+}
+
+/// Fails on an edge without a `weight` attribute, and on a vertex of `max_n` or more.
+pub fn read_weighted_dot<W>(
+    text: &str,
+    max_n: usize,
+) -> Result<WeightedAdjacencyLists<W>, ParseError>
+where
+    W: Weight + FromStr,
+{
+    let (n, edges) = read(text, max_n, |edge, attributes| {
+        match attributes.iter().find(|(name, _)| name.text == "weight") {
+            Some((_, value)) => value
+                .text
+                .parse()
+                .map_err(|_| value.error("invalid number")),
+            None => Err(edge.error("missing weight")),
+        }
+    })?;
+
+    let mut graph = WeightedAdjacencyLists::new(n);
+    for (i, j, weight) in edges {
+        graph.add_edge(i, j, weight).unwrap();
+    }
+    Ok(graph)
+}
+
+pub fn write_dot<G: Graph + ?Sized>(graph: &G) -> String {
+    let mut text = String::from("digraph {\n");
+    for i in 0..graph.n() {
+        text.push_str(&format!("  {};\n", i));
+    }
+    for i in 0..graph.n() {
+        for j in graph.out_edges(i) {
+            text.push_str(&format!("  {} -> {};\n", i, j));
+        }
+    }
+    text.push_str("}\n");
+    text
+}
+
+pub fn write_weighted_dot<W, G>(graph: &G) -> String
+where
+    W: Weight + Display,
+    G: WeightedGraph<W> + ?Sized,
+{
+    let mut text = String::from("digraph {\n");
+    for i in 0..graph.n() {
+        text.push_str(&format!("  {};\n", i));
+    }
+    for i in 0..graph.n() {
+        for (j, weight) in graph.out_edges(i) {
+            text.push_str(&format!("  {} -> {} [weight={}];\n", i, j, weight));
+        }
+    }
+    text.push_str("}\n");
+    text
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Id,
+    DirectedEdge,
+    UndirectedEdge,
+    Punctuation,
+    End,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    text: String,
+    line: usize,
+    column: usize,
+}
+
+impl Token {
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError::new(self.line, self.column, message)
+    }
+
+    fn is(&self, text: &str) -> bool {
+        self.kind != Kind::Id && self.text == text
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == Kind::Id && self.text.eq_ignore_ascii_case(keyword)
+    }
+}
+
+// the vertex count and the edges, with the weight read from the edge token and the attributes
+fn read<W, F>(text: &str, max_n: usize, mut weight: F) -> Result<(usize, Edges<W>), ParseError>
+where
+    W: Clone,
+    F: FnMut(&Token, &[(Token, Token)]) -> Result<W, ParseError>,
+{
+    let tokens = tokenize(text)?;
+    let mut index = 0;
+    let mut next = || {
+        let token = tokens[index].clone();
+        index = std::cmp::min(index + 1, tokens.len() - 1);
+        token
+    };
+
+    let mut token = next();
+    if token.is_keyword("strict") {
+        token = next();
+    }
+    let is_directed = if token.is_keyword("digraph") {
+        true
+    } else if token.is_keyword("graph") {
+        false
+    } else {
+        return Err(token.error("expected graph or digraph"));
+    };
+    let edge_kind = if is_directed {
+        Kind::DirectedEdge
+    } else {
+        Kind::UndirectedEdge
+    };
+
+    token = next();
+    if token.kind == Kind::Id {
+        token = next();
+    }
+    if !token.is("{") {
+        return Err(token.error("expected {"));
+    }
+
+    let mut n = 0;
+    let mut edges = vec![];
+    token = next();
+    while !token.is("}") {
+        if token.kind == Kind::End {
+            return Err(token.error("unexpected end"));
+        }
+        if token.kind != Kind::Id {
+            return Err(token.error("expected a statement"));
+        }
+
+        if ["graph", "node", "edge"]
+            .iter()
+            .any(|keyword| token.is_keyword(keyword))
+        {
+            token = next();
+            read_attributes(&mut token, &mut next)?;
+        } else if token.is_keyword("subgraph") {
+            return Err(token.error("unsupported subgraph"));
+        } else {
+            let first = token.clone();
+            token = next();
+            if token.is("=") {
+                // a graph attribute
+                token = next();
+                if token.kind != Kind::Id {
+                    return Err(token.error("expected an id"));
+                }
+                token = next();
+            } else {
+                let mut vertices = vec![vertex(&first, max_n)?];
+                while token.kind == edge_kind {
+                    token = next();
+                    vertices.push(vertex(&token, max_n)?);
+                    token = next();
+                }
+                if token.kind == Kind::DirectedEdge || token.kind == Kind::UndirectedEdge {
+                    return Err(token.error("unexpected edge operator"));
+                }
+
+                let attributes = read_attributes(&mut token, &mut next)?;
+                for vertex in vertices.iter() {
+                    n = std::cmp::max(n, vertex + 1);
+                }
+                if vertices.len() > 1 {
+                    let weight = weight(&first, &attributes)?;
+                    for pair in vertices.windows(2) {
+                        if !is_directed {
+                            edges.push((pair[1], pair[0], weight.clone()));
+                        }
+                        edges.push((pair[0], pair[1], weight.clone()));
+                    }
+                }
+            }
+        }
+
+        if token.is(";") {
+            token = next();
+        }
+    }
+
+    token = next();
+    if token.kind != Kind::End {
+        return Err(token.error("unexpected token after the graph"));
+    }
+    Ok((n, edges))
+}
+
+// the `name = value` pairs of the attribute lists at `token`, leaving it after them
+fn read_attributes<F>(token: &mut Token, next: &mut F) -> Result<Vec<(Token, Token)>, ParseError>
+where
+    F: FnMut() -> Token,
+{
+    let mut attributes = vec![];
+    while token.is("[") {
+        *token = next();
+        while !token.is("]") {
+            let name = token.clone();
+            if name.kind != Kind::Id {
+                return Err(name.error("expected an attribute"));
+            }
+            *token = next();
+            if !token.is("=") {
+                return Err(token.error("expected ="));
+            }
+            let value = next();
+            if value.kind != Kind::Id {
+                return Err(value.error("expected an id"));
+            }
+            attributes.push((name, value));
+
+            *token = next();
+            if token.is(";") || token.is(",") {
+                *token = next();
+            }
+        }
+        *token = next();
+    }
+    Ok(attributes)
+}
+
+fn vertex(token: &Token, max_n: usize) -> Result<usize, ParseError> {
+    if token.kind != Kind::Id {
+        return Err(token.error("expected a vertex"));
+    }
+    match token.text.parse::<usize>() {
+        Ok(vertex) if vertex < max_n => Ok(vertex),
+        Ok(_) => Err(token.error("too many vertices")),
+        _ => Err(token.error("invalid vertex")),
+    }
+}
+
+// ends with a token of kind `End`
+fn tokenize(text: &str) -> Result<Vec<Token>, ParseError> {
+    let characters: Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+    let (mut index, mut line, mut column) = (0, 1, 1);
+    let at = |index: usize| characters.get(index).copied();
+
+    while let Some(character) = at(index) {
+        let (start_line, start_column) = (line, column);
+        let start = index;
+
+        let kind = if character == '\n' {
+            line += 1;
+            column = 1;
+            index += 1;
+            continue;
+        } else if character.is_whitespace() {
+            None
+        } else if character == '#' && column == 1 {
+            // a preprocessor output line
+            while at(index + 1).is_some_and(|next| next != '\n') {
+                index += 1;
+                column += 1;
+            }
+            None
+        } else if character == '/' && at(index + 1) == Some('/') {
+            while at(index + 1).is_some_and(|next| next != '\n') {
+                index += 1;
+                column += 1;
+            }
+            None
+        } else if character == '/' && at(index + 1) == Some('*') {
+            index += 1;
+            loop {
+                index += 1;
+                column += 1;
+                match at(index) {
+                    Some('*') if at(index + 1) == Some('/') => break,
+                    Some('\n') => {
+                        line += 1;
+                        column = 0;
+                    }
+                    Some(_) => (),
+                    None => {
+                        return Err(ParseError::new(
+                            start_line,
+                            start_column,
+                            "unterminated comment",
+                        ))
+                    }
+                }
+            }
+            index += 1;
+            column += 1;
+            None
+        } else if character == '-' && at(index + 1) == Some('>') {
+            index += 1;
+            column += 1;
+            Some(Kind::DirectedEdge)
+        } else if character == '-' && at(index + 1) == Some('-') {
+            index += 1;
+            column += 1;
+            Some(Kind::UndirectedEdge)
+        } else if "{}[];,=".contains(character) {
+            Some(Kind::Punctuation)
+        } else if character == '"' {
+            let mut text = String::new();
+            loop {
+                index += 1;
+                column += 1;
+                match at(index) {
+                    Some('"') => break,
+                    Some('\\') if at(index + 1) == Some('"') => {
+                        index += 1;
+                        column += 1;
+                        text.push('"');
+                    }
+                    Some('\n') => {
+                        return Err(ParseError::new(
+                            start_line,
+                            start_column,
+                            "unterminated string",
+                        ))
+                    }
+                    Some(character) => text.push(character),
+                    None => {
+                        return Err(ParseError::new(
+                            start_line,
+                            start_column,
+                            "unterminated string",
+                        ))
+                    }
+                }
+            }
+            tokens.push(Token {
+                kind: Kind::Id,
+                text,
+                line: start_line,
+                column: start_column,
+            });
+            index += 1;
+            column += 1;
+            continue;
+        } else if is_id(character) || character == '-' {
+            while at(index + 1).is_some_and(is_id) {
+                index += 1;
+                column += 1;
+            }
+            Some(Kind::Id)
+        } else {
+            return Err(ParseError::new(line, column, "unexpected character"));
+        };
+
+        index += 1;
+        column += 1;
+        if let Some(kind) = kind {
+            tokens.push(Token {
+                kind,
+                text: characters[start..index].iter().collect(),
+                line: start_line,
+                column: start_column,
+            });
+        }
+    }
+
+    tokens.push(Token {
+        kind: Kind::End,
+        text: String::new(),
+        line,
+        column,
+    });
+    Ok(tokens)
+}
+
+fn is_id(character: char) -> bool {
+    character.is_alphanumeric() || character == '_' || character == '.'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_dot, read_weighted_dot, write_dot, write_weighted_dot};
+    use crate::graph::parse::ParseError;
+    use crate::graph::{AdjacencyMatrix, Graph, WeightedAdjacencyLists, WeightedGraph};
+
+    #[test]
+    pub fn round_trip() {
+        let mut graph = AdjacencyMatrix::new(3);
+        graph.add_edge(0, 2).unwrap();
+        graph.add_edge(2, 2).unwrap();
+
+        let text = write_dot(&graph);
+        assert_eq!(
+            text,
+            "digraph {\n  0;\n  1;\n  2;\n  0 -> 2;\n  2 -> 2;\n}\n"
+        );
+        assert_eq!(write_dot(&read_dot(&text, 100).unwrap()), text);
+
+        let mut graph = WeightedAdjacencyLists::new(2);
+        graph.add_edge(1, 0, -3).unwrap();
+        let text = write_weighted_dot(&graph);
+        let read = read_weighted_dot::<i64>(&text, 100).unwrap();
+        assert_eq!(read.weight(1, 0), Some(&-3));
+        assert_eq!(write_weighted_dot(&read), text);
+    }
+
+    #[test]
+    pub fn dot_features() {
+        let text = r#"
+            /* from
+               another tool */
+            strict graph "network" {
+                rankdir = LR; // left to right
+                node [shape=circle];
+                "0" -- 1 -- 2 [weight=4, color="red"]
+                3
+            }
+        "#;
+        let graph = read_weighted_dot::<u32>(text, 100).unwrap();
+        assert_eq!(graph.n(), 4);
+        assert_eq!(graph.weight(0, 1), Some(&4));
+        assert_eq!(graph.weight(1, 0), Some(&4));
+        assert_eq!(graph.weight(2, 1), Some(&4));
+        assert_eq!(graph.out_edges(3).iter().count(), 0);
+    }
+
+    #[test]
+    pub fn errors() {
+        assert_eq!(
+            read_dot("digraph {\n  0 -> a;\n}", 100).err(),
+            Some(ParseError::new(2, 8, "invalid vertex"))
+        );
+        assert_eq!(
+            read_dot("digraph {\n  0 -- 1;\n}", 100).err(),
+            Some(ParseError::new(2, 5, "unexpected edge operator"))
+        );
+        assert_eq!(
+            read_dot("digraph {\n  0 -> 1 [weight 2];\n}", 100).err(),
+            Some(ParseError::new(2, 18, "expected ="))
+        );
+        assert_eq!(
+            read_weighted_dot::<u32>("digraph { 0 -> 1 }", 100).err(),
+            Some(ParseError::new(1, 11, "missing weight"))
+        );
+        assert_eq!(
+            read_dot("digraph { 0 -> 1 ", 100).err(),
+            Some(ParseError::new(1, 18, "unexpected end"))
+        );
+        assert_eq!(
+            read_dot("digraph { \"0 }", 100).err(),
+            Some(ParseError::new(1, 11, "unterminated string"))
+        );
+        assert_eq!(
+            read_dot("digraph { 0 -> 99999999999999999 }", 100).err(),
+            Some(ParseError::new(1, 16, "too many vertices"))
+        );
+        assert_eq!(read_dot("digraph { 99 }", 100).unwrap().n(), 100);
+    }
+}
@@ -0,0 +1,171 @@
+//! Plain edge lists, a line `i j` per edge or `i j weight` for weighted graphs. A line with a
+//! single vertex declares it without edges, and lines starting with `#` are comments. The vertex
+//! count is the greatest vertex plus one.
+
+use super::parse::{lines, Edges, Line, ParseError};
+use super::{AdjacencyLists, Graph, Weight, WeightedAdjacencyLists, WeightedGraph};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Fails on a vertex of `max_n` or more, which would not fit in a graph of `max_n` vertices.
+pub fn read_edge_list(text: &str, max_n: usize) -> Result<AdjacencyLists, ParseError> {
+    let (n, edges) = read(text, 2, max_n, |_| Ok(()))?;
+
+    let mut graph = AdjacencyLists::new(n);
+    for (i, j, _) in edges {
+        graph.add_edge(i, j).unwrap();
+    }
+    Ok(graph)
+}
+
+/// Fails on a vertex of `max_n` or more, which would not fit in a graph of `max_n` vertices.
+pub fn read_weighted_edge_list<W>(
+    text: &str,
+    max_n: usize,
+) -> Result<WeightedAdjacencyLists<W>, ParseError>
+where
+    W: Weight + FromStr,
+{
+    let (n, edges) = read(text, 3, max_n, |line| line.field(2))?;
+
+    let mut graph = WeightedAdjacencyLists::new(n);
+    for (i, j, weight) in edges {
+        graph.add_edge(i, j, weight).unwrap();
+    }
+    Ok(graph)
+}
+
+pub fn write_edge_list<G: Graph + ?Sized>(graph: &G) -> String {
+    let mut text = String::new();
+    let mut has_edge = vec![false; graph.n()];
+    for i in 0..graph.n() {
+        for j in graph.out_edges(i) {
+            has_edge[i] = true;
+            has_edge[j] = true;
+            text.push_str(&format!("{} {}\n", i, j));
+        }
+    }
+    write_isolated(&mut text, &has_edge);
+    text
+}
+
+pub fn write_weighted_edge_list<W, G>(graph: &G) -> String
+where
+    W: Weight + Display,
+    G: WeightedGraph<W> + ?Sized,
+{
+    let mut text = String::new();
+    let mut has_edge = vec![false; graph.n()];
+    for i in 0..graph.n() {
+        for (j, weight) in graph.out_edges(i) {
+            has_edge[i] = true;
+            has_edge[j] = true;
+            text.push_str(&format!("{} {} {}\n", i, j, weight));
+        }
+    }
+    write_isolated(&mut text, &has_edge);
+    text
+}
+
+fn write_isolated(text: &mut String, has_edge: &[bool]) {
+    for (vertex, has_edge) in has_edge.iter().enumerate() {
+        if !has_edge {
+            text.push_str(&format!("{}\n", vertex));
+        }
+    }
+}
+
+// the vertex count and the edges of lines with `len` fields
+fn read<W, F>(
+    text: &str,
+    len: usize,
+    max_n: usize,
+    mut weight: F,
+) -> Result<(usize, Edges<W>), ParseError>
+where
+    F: FnMut(&Line) -> Result<W, ParseError>,
+{
+    let mut n = 0;
+    let mut edges = vec![];
+    for line in lines(text, "#") {
+        if line.len() == 1 {
+            n = std::cmp::max(n, vertex(&line, 0, max_n)? + 1);
+            continue;
+        }
+
+        line.expect_len(len)?;
+        let (i, j) = (vertex(&line, 0, max_n)?, vertex(&line, 1, max_n)?);
+        n = std::cmp::max(n, std::cmp::max(i, j) + 1);
+        edges.push((i, j, weight(&line)?));
+    }
+    Ok((n, edges))
+}
+
+fn vertex(line: &Line, index: usize, max_n: usize) -> Result<usize, ParseError> {
+    let vertex: usize = line.field(index)?;
+    if vertex >= max_n {
+        return Err(line.error(index, "too many vertices"));
+    }
+    Ok(vertex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        read_edge_list, read_weighted_edge_list, write_edge_list, write_weighted_edge_list,
+    };
+    use crate::graph::parse::ParseError;
+    use crate::graph::{AdjacencyMatrix, Graph, WeightedAdjacencyLists, WeightedGraph};
+
+    #[test]
+    pub fn round_trip() {
+        let mut graph = AdjacencyMatrix::new(5);
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(3, 1).unwrap();
+        graph.add_edge(1, 1).unwrap();
+
+        let text = write_edge_list(&graph);
+        assert_eq!(text, "0 1\n1 1\n3 1\n2\n4\n");
+        let read = read_edge_list(&text, 100).unwrap();
+        assert_eq!(read.n(), 5);
+        assert_eq!(write_edge_list(&read), text);
+
+        let mut graph = WeightedAdjacencyLists::new(3);
+        graph.add_edge(2, 0, -4).unwrap();
+        graph.add_edge(0, 1, 7).unwrap();
+        let text = write_weighted_edge_list(&graph);
+        let read = read_weighted_edge_list::<i32>(&text, 100).unwrap();
+        assert_eq!(read.weight(2, 0), Some(&-4));
+        assert_eq!(write_weighted_edge_list(&read), text);
+    }
+
+    #[test]
+    pub fn errors() {
+        let text = "# comment\n\n0 1\n  1 x\n";
+        assert_eq!(
+            read_edge_list(text, 100).err(),
+            Some(ParseError::new(4, 5, "invalid number"))
+        );
+        assert_eq!(
+            read_edge_list("0 1 2", 100).err(),
+            Some(ParseError::new(1, 5, "unexpected field"))
+        );
+        assert_eq!(
+            read_weighted_edge_list::<u8>("0 1", 100).err(),
+            Some(ParseError::new(1, 4, "missing field"))
+        );
+        assert_eq!(
+            read_weighted_edge_list::<u8>("0 1 256", 100).err(),
+            Some(ParseError::new(1, 5, "invalid number"))
+        );
+        assert_eq!(
+            read_edge_list("99999999999999999 0", 100).err(),
+            Some(ParseError::new(1, 1, "too many vertices"))
+        );
+        assert_eq!(
+            read_edge_list("0 1\n0 100\n", 100).err(),
+            Some(ParseError::new(2, 3, "too many vertices"))
+        );
+        assert_eq!(read_edge_list("0 99\n", 100).unwrap().n(), 100);
+    }
+}
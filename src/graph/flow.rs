@@ -0,0 +1,334 @@
+//! Maximum flows from a source to a sink, reading the weight of every edge as its capacity.
+
+use super::{Weight, WeightedGraph};
+use crate::array_queue::ArrayQueue;
+use crate::CollectionError;
+use crate::Queue;
+use crate::{INVALID_ARGUMENT_ERROR, OUT_OF_BOUND_ERROR};
+
+/// A maximum flow with the minimum cut it saturates.
+#[derive(Debug, Clone)]
+pub struct MaxFlow<W> {
+    value: W,
+    network: Residual<W>,
+    // the vertices still reachable from the source in the residual network
+    is_source_side: Vec<bool>,
+}
+
+impl<W: Weight> MaxFlow<W> {
+    fn new(value: W, network: Residual<W>, source: usize) -> MaxFlow<W> {
+        let is_source_side = network.levels(source).iter().map(Option::is_some).collect();
+
+        MaxFlow {
+            value,
+            network,
+            is_source_side,
+        }
+    }
+
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    /// The flow through the edge `(i, j)`, zero when there is no such edge.
+    pub fn flow(&self, i: usize, j: usize) -> W {
+        let network = &self.network;
+        let edge = network.edges.get(i).and_then(|edges| {
+            edges
+                .iter()
+                .find(|edge| **edge % 2 == 0 && network.heads[**edge] == j)
+        });
+
+        match edge {
+            Some(edge) => network.capacities[*edge] - network.residuals[*edge],
+            None => W::zero(),
+        }
+    }
+
+    /// The vertices on the source side of a minimum cut.
+    pub fn source_side(&self) -> Vec<usize> {
+        (0..self.is_source_side.len())
+            .filter(|vertex| self.is_source_side[*vertex])
+            .collect()
+    }
+
+    /// The edges of a minimum cut with their capacities, which add up to the flow value.
+    pub fn min_cut(&self) -> Vec<(usize, usize, W)> {
+        let network = &self.network;
+        let mut edges = vec![];
+        for vertex in self.source_side() {
+            for edge in network.edges[vertex].iter().filter(|edge| **edge % 2 == 0) {
+                let head = network.heads[*edge];
+                if !self.is_source_side[head] {
+                    edges.push((vertex, head, network.capacities[*edge]));
+                }
+            }
+        }
+        edges
+    }
+}
+
+/// Dinic's algorithm in O(n^2 m), by blocking flows along shortest paths. Fails on a negative
+/// capacity or when `source` is `sink`.
+pub fn dinic<W, G>(graph: &G, source: usize, sink: usize) -> Result<MaxFlow<W>, CollectionError>
+where
+    W: Weight,
+    G: WeightedGraph<W> + ?Sized,
+{
+    let mut network = Residual::new(graph, source, sink)?;
+    let mut value = W::zero();
+
+    loop {
+        let levels = network.levels(source);
+        if levels[sink].is_none() {
+            break;
+        }
+
+        // the next edge to try from each vertex, the edges before it lead to dead ends
+        let mut next = vec![0; graph.n()];
+        let mut path = vec![];
+        let mut vertex = source;
+        loop {
+            if vertex == sink {
+                value = value + network.augment(&path);
+                path.clear();
+                vertex = source;
+                continue;
+            }
+
+            let edges = &network.edges[vertex];
+            while next[vertex] < edges.len() {
+                let edge = edges[next[vertex]];
+                let head = network.heads[edge];
+                if network.residuals[edge] > W::zero()
+                    && levels[head] == levels[vertex].map(|level| level + 1)
+                {
+                    break;
+                }
+                next[vertex] += 1;
+            }
+
+            if next[vertex] < edges.len() {
+                let edge = edges[next[vertex]];
+                path.push(edge);
+                vertex = network.heads[edge];
+                continue;
+            }
+
+            // a dead end, retreat and skip the edge that led here
+            match path.pop() {
+                Some(edge) => {
+                    vertex = network.heads[edge ^ 1];
+                    next[vertex] += 1;
+                }
+                None => break,
+            }
+        }
+    }
+
+    Ok(MaxFlow::new(value, network, source))
+}
+
+/// Edmonds-Karp in O(n m^2), by augmenting along a shortest path each time. Fails on a negative
+/// capacity or when `source` is `sink`.
+pub fn edmonds_karp<W, G>(
+    graph: &G,
+    source: usize,
+    sink: usize,
+) -> Result<MaxFlow<W>, CollectionError>
+where
+    W: Weight,
+    G: WeightedGraph<W> + ?Sized,
+{
+    let mut network = Residual::new(graph, source, sink)?;
+    let mut value = W::zero();
+
+    loop {
+        // the edge each vertex was reached through
+        let mut parents = vec![None; graph.n()];
+        let mut queue = ArrayQueue::new();
+        queue.enqueue(source);
+        while let Some(vertex) = queue.dequeue() {
+            for edge in network.edges[vertex].iter() {
+                let head = network.heads[*edge];
+                if head != source && parents[head].is_none() && network.residuals[*edge] > W::zero()
+                {
+                    parents[head] = Some(*edge);
+                    queue.enqueue(head);
+                }
+            }
+            if parents[sink].is_some() {
+                break;
+            }
+        }
+
+        if parents[sink].is_none() {
+            break;
+        }
+
+        let mut path = vec![];
+        let mut vertex = sink;
+        while let Some(edge) = parents[vertex] {
+            path.push(edge);
+            vertex = network.heads[edge ^ 1];
+        }
+        value = value + network.augment(&path);
+    }
+
+    Ok(MaxFlow::new(value, network, source))
+}
+
+// every edge is followed by its reverse, so that the reverse of `edge` is `edge ^ 1`
+#[derive(Debug, Clone)]
+struct Residual<W> {
+    heads: Vec<usize>,
+    capacities: Vec<W>,
+    residuals: Vec<W>,
+    // the edges out of each vertex, reverse ones included
+    edges: Vec<Vec<usize>>,
+}
+
+impl<W: Weight> Residual<W> {
+    fn new<G>(graph: &G, source: usize, sink: usize) -> Result<Residual<W>, CollectionError>
+    where
+        G: WeightedGraph<W> + ?Sized,
+    {
+        if source >= graph.n() || sink >= graph.n() {
+            return Err(OUT_OF_BOUND_ERROR);
+        }
+        if source == sink {
+            return Err(INVALID_ARGUMENT_ERROR);
+        }
+
+        let mut network = Residual {
+            heads: vec![],
+            capacities: vec![],
+            residuals: vec![],
+            edges: vec![vec![]; graph.n()],
+        };
+        for vertex in 0..graph.n() {
+            for (head, capacity) in graph.out_edges(vertex) {
+                if capacity < W::zero() {
+                    return Err(INVALID_ARGUMENT_ERROR);
+                }
+                if head != vertex {
+                    network.add(vertex, head, capacity);
+                }
+            }
+        }
+        Ok(network)
+    }
+
+    fn add(&mut self, tail: usize, head: usize, capacity: W) {
+        self.edges[tail].push(self.heads.len());
+        self.heads.push(head);
+        self.capacities.push(capacity);
+        self.residuals.push(capacity);
+
+        self.edges[head].push(self.heads.len());
+        self.heads.push(tail);
+        self.capacities.push(W::zero());
+        self.residuals.push(W::zero());
+    }
+
+    // the number of residual edges from `source` to each vertex it reaches
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.edges.len()];
+        levels[source] = Some(0);
+
+        let mut queue = ArrayQueue::new();
+        queue.enqueue((source, 0));
+        while let Some((vertex, level)) = queue.dequeue() {
+            for edge in self.edges[vertex].iter() {
+                let head = self.heads[*edge];
+                if levels[head].is_none() && self.residuals[*edge] > W::zero() {
+                    levels[head] = Some(level + 1);
+                    queue.enqueue((head, level + 1));
+                }
+            }
+        }
+        levels
+    }
+
+    // pushes the bottleneck of the non-empty `path` through it
+    fn augment(&mut self, path: &[usize]) -> W {
+        let bottleneck = path.iter().map(|edge| self.residuals[*edge]).min().unwrap();
+
+        for edge in path.iter() {
+            self.residuals[*edge] = self.residuals[*edge] - bottleneck;
+            self.residuals[*edge ^ 1] = self.residuals[*edge ^ 1] + bottleneck;
+        }
+        bottleneck
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dinic, edmonds_karp};
+    use crate::graph::{WeightedAdjacencyLists, WeightedGraph};
+    use crate::{INVALID_ARGUMENT_ERROR, OUT_OF_BOUND_ERROR};
+
+    // the example of CLRS, with a maximum flow of 23
+    fn network() -> WeightedAdjacencyLists<u32> {
+        let mut graph = WeightedAdjacencyLists::new(6);
+        for (i, j, capacity) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ]
+        .iter()
+        {
+            graph.add_edge(*i, *j, *capacity).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    pub fn max_flow() {
+        let graph = network();
+
+        for flow in [dinic(&graph, 0, 5), edmonds_karp(&graph, 0, 5)].iter() {
+            let flow = flow.as_ref().unwrap();
+            assert_eq!(flow.value(), 23);
+
+            // the flow is conserved and within the capacities
+            for vertex in 1..5 {
+                let flow_in: u32 = (0..6).map(|i| flow.flow(i, vertex)).sum();
+                let flow_out: u32 = (0..6).map(|j| flow.flow(vertex, j)).sum();
+                assert_eq!(flow_in, flow_out);
+            }
+            assert!(flow.flow(0, 1) <= 16);
+            assert_eq!(flow.flow(5, 0), 0);
+
+            assert_eq!(flow.source_side(), vec![0, 1, 2, 4]);
+            let mut cut = flow.min_cut();
+            cut.sort();
+            assert_eq!(cut, vec![(1, 3, 12), (4, 3, 7), (4, 5, 4)]);
+        }
+
+        let flow = dinic(&graph, 5, 0).unwrap();
+        assert_eq!(flow.value(), 0);
+        assert_eq!(flow.source_side(), vec![5]);
+    }
+
+    #[test]
+    pub fn invalid_network() {
+        let mut graph = network();
+        assert_eq!(dinic(&graph, 0, 0).err(), Some(INVALID_ARGUMENT_ERROR));
+        assert_eq!(edmonds_karp(&graph, 0, 6).err(), Some(OUT_OF_BOUND_ERROR));
+
+        let mut negative = WeightedAdjacencyLists::new(2);
+        negative.add_edge(0, 1, -1).unwrap();
+        assert_eq!(dinic(&negative, 0, 1).err(), Some(INVALID_ARGUMENT_ERROR));
+
+        // a self loop carries no flow
+        graph.add_edge(3, 3, 100).unwrap();
+        assert_eq!(edmonds_karp(&graph, 0, 5).unwrap().value(), 23);
+    }
+}
@@ -0,0 +1,187 @@
+use super::Graph;
+use crate::array_queue::ArrayQueue;
+use crate::CollectionError;
+use crate::Queue;
+use crate::{INVALID_ARGUMENT_ERROR, OUT_OF_BOUND_ERROR};
+
+/// A set of edges without a common vertex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matching {
+    mates: Vec<Option<usize>>,
+}
+
+impl Matching {
+    /// The number of matched edges.
+    pub fn size(&self) -> usize {
+        self.mates.iter().filter(|mate| mate.is_some()).count() / 2
+    }
+
+    pub fn mate(&self, vertex: usize) -> Option<usize> {
+        *self.mates.get(vertex)?
+    }
+
+    /// The matched edges `(i, j)` with `i < j`.
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        (0..self.mates.len())
+            .filter_map(|i| match self.mates[i] {
+                Some(j) if i < j => Some((i, j)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Hopcroft-Karp in O(m sqrt(n)), a maximum matching of the bipartite graph between the left
+/// vertices `0..left` and the right vertices `left..n`. Only the edges out of the left vertices
+/// are read, and they must all lead to right vertices.
+pub fn hopcroft_karp<G: Graph + ?Sized>(
+    graph: &G,
+    left: usize,
+) -> Result<Matching, CollectionError> {
+    if left > graph.n() {
+        return Err(OUT_OF_BOUND_ERROR);
+    }
+
+    let mut neighbours = Vec::with_capacity(left);
+    for vertex in 0..left {
+        let edges: Vec<usize> = graph.out_edges(vertex).into_iter().collect();
+        if edges.iter().any(|neighbour| *neighbour < left) {
+            return Err(INVALID_ARGUMENT_ERROR);
+        }
+        neighbours.push(edges);
+    }
+
+    let mut mates = vec![None; graph.n()];
+    // each phase augments along a maximal set of disjoint shortest alternating paths
+    while let Some((mut levels, length)) = alternating_levels(&neighbours, &mates) {
+        let mut next = vec![0; left];
+        for root in 0..left {
+            if mates[root].is_none() {
+                augment(
+                    root,
+                    length,
+                    &neighbours,
+                    &mut levels,
+                    &mut next,
+                    &mut mates,
+                );
+            }
+        }
+    }
+
+    Ok(Matching { mates })
+}
+
+// the number of left vertices before each left vertex on the shortest alternating paths from the
+// free left vertices, with the level of the last left vertex of the shortest augmenting paths.
+// `None` when no path reaches a free right vertex
+fn alternating_levels(
+    neighbours: &[Vec<usize>],
+    mates: &[Option<usize>],
+) -> Option<(Vec<Option<usize>>, usize)> {
+    let mut levels = vec![None; neighbours.len()];
+    let mut queue = ArrayQueue::new();
+    for vertex in 0..neighbours.len() {
+        if mates[vertex].is_none() {
+            levels[vertex] = Some(0);
+            queue.enqueue((vertex, 0));
+        }
+    }
+
+    let mut length = None;
+    while let Some((vertex, level)) = queue.dequeue() {
+        if length.is_some_and(|length| level > length) {
+            break;
+        }
+
+        for neighbour in neighbours[vertex].iter() {
+            match mates[*neighbour] {
+                None => length = Some(level),
+                Some(mate) if levels[mate].is_none() => {
+                    levels[mate] = Some(level + 1);
+                    queue.enqueue((mate, level + 1));
+                }
+                Some(_) => (),
+            }
+        }
+    }
+
+    Some((levels, length?))
+}
+
+// an iterative depth-first search for a shortest augmenting path from the free `root` along the
+// levels, which flips the path when it ends in a free right vertex
+fn augment(
+    root: usize,
+    length: usize,
+    neighbours: &[Vec<usize>],
+    levels: &mut [Option<usize>],
+    next: &mut [usize],
+    mates: &mut [Option<usize>],
+) {
+    let mut path = vec![root];
+    // the right vertex taken from each vertex of the path but the last
+    let mut rights: Vec<usize> = vec![];
+
+    while let Some(&vertex) = path.last() {
+        if next[vertex] == neighbours[vertex].len() {
+            // a dead end for this phase
+            levels[vertex] = None;
+            path.pop();
+            rights.pop();
+            continue;
+        }
+
+        let right = neighbours[vertex][next[vertex]];
+        next[vertex] += 1;
+        match mates[right] {
+            None if levels[vertex] == Some(length) => {
+                rights.push(right);
+                for (left, right) in path.iter().zip(rights.iter()) {
+                    mates[*left] = Some(*right);
+                    mates[*right] = Some(*left);
+                }
+                return;
+            }
+            Some(mate) if levels[mate] == levels[vertex].map(|level| level + 1) => {
+                rights.push(right);
+                path.push(mate);
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hopcroft_karp;
+    use crate::graph::{AdjacencyLists, Graph};
+    use crate::{INVALID_ARGUMENT_ERROR, OUT_OF_BOUND_ERROR};
+
+    #[test]
+    pub fn bipartite_matching() {
+        // workers 0..4 and jobs 4..8
+        let mut graph = AdjacencyLists::new(8);
+        for (worker, job) in [(0, 4), (0, 5), (1, 4), (2, 5), (2, 6), (3, 6), (3, 7)].iter() {
+            graph.add_edge(*worker, *job).unwrap();
+        }
+
+        let matching = hopcroft_karp(&graph, 4).unwrap();
+        assert_eq!(matching.size(), 4);
+        assert_eq!(matching.mate(1), Some(4));
+        assert_eq!(matching.mate(4), Some(1));
+        assert_eq!(matching.edges(), vec![(0, 5), (1, 4), (2, 6), (3, 7)]);
+
+        // two workers for one job
+        let mut graph = AdjacencyLists::new(3);
+        graph.add_edge(0, 2).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        let matching = hopcroft_karp(&graph, 2).unwrap();
+        assert_eq!(matching.size(), 1);
+        assert_eq!(matching.mate(1), None);
+
+        assert_eq!(hopcroft_karp(&graph, 4), Err(OUT_OF_BOUND_ERROR));
+        graph.add_edge(0, 1).unwrap();
+        assert_eq!(hopcroft_karp(&graph, 2), Err(INVALID_ARGUMENT_ERROR));
+    }
+}
@@ -0,0 +1,132 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error of a graph reader, at a line and a column counted from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: &'static str,
+}
+
+impl ParseError {
+    pub(in crate) const fn new(line: usize, column: usize, message: &'static str) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message,
+        }
+    }
+
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    pub const fn message(&self) -> &'static str {
+        self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error: {} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// The edges read from a text, with their weights.
+pub(in crate) type Edges<W> = Vec<(usize, usize, W)>;
+
+/// The whitespace separated fields of a line, with their columns.
+#[derive(Debug)]
+pub(in crate) struct Line<'a> {
+    number: usize,
+    fields: Vec<(usize, &'a str)>,
+    // the column after the last character
+    end: usize,
+}
+
+impl<'a> Line<'a> {
+    fn new(number: usize, text: &'a str) -> Line<'a> {
+        let mut fields = vec![];
+        let mut start = None;
+        let mut column = 1;
+        for (index, character) in text.char_indices() {
+            match (character.is_whitespace(), start) {
+                (false, None) => start = Some((column, index)),
+                (true, Some((start_column, start_index))) => {
+                    fields.push((start_column, &text[start_index..index]));
+                    start = None;
+                }
+                _ => (),
+            }
+            column += 1;
+        }
+        if let Some((start_column, start_index)) = start {
+            fields.push((start_column, &text[start_index..]));
+        }
+
+        Line {
+            number,
+            fields,
+            end: column,
+        }
+    }
+
+    pub(in crate) fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub(in crate) fn text(&self, index: usize) -> Option<&'a str> {
+        self.fields.get(index).map(|field| field.1)
+    }
+
+    pub(in crate) fn column(&self, index: usize) -> usize {
+        match self.fields.get(index) {
+            Some((column, _)) => *column,
+            None => self.end,
+        }
+    }
+
+    pub(in crate) fn error(&self, index: usize, message: &'static str) -> ParseError {
+        ParseError::new(self.number, self.column(index), message)
+    }
+
+    pub(in crate) fn field<T: FromStr>(&self, index: usize) -> Result<T, ParseError> {
+        match self.text(index) {
+            Some(text) => text
+                .parse()
+                .map_err(|_| self.error(index, "invalid number")),
+            None => Err(self.error(index, "missing field")),
+        }
+    }
+
+    /// Fails unless the line has `len` fields.
+    pub(in crate) fn expect_len(&self, len: usize) -> Result<(), ParseError> {
+        if self.len() < len {
+            return Err(self.error(self.len(), "missing field"));
+        }
+        if self.len() > len {
+            return Err(self.error(len, "unexpected field"));
+        }
+        Ok(())
+    }
+}
+
+/// The lines with a field, but the ones starting with `comment`.
+pub(in crate) fn lines<'a>(text: &'a str, comment: &'a str) -> impl Iterator<Item = Line<'a>> {
+    text.lines()
+        .enumerate()
+        .map(|(index, text)| Line::new(index + 1, text))
+        .filter(move |line| line.len() > 0 && !line.text(0).unwrap().starts_with(comment))
+}
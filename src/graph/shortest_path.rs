@@ -0,0 +1,399 @@
+use super::{Weight, WeightedGraph};
+use crate::array_deque::ArrayDeque;
+use crate::indexed_binary_heap::IndexedBinaryHeap;
+use crate::CollectionError;
+use crate::{AddressablePriorityQueue, Deque, PriorityQueue};
+use crate::{INVALID_ARGUMENT_ERROR, NEGATIVE_CYCLE_ERROR, OUT_OF_BOUND_ERROR};
+
+/// The shortest paths from a source to every vertex, as a tree of parents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<W> {
+    source: usize,
+    distances: Vec<Option<W>>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<W: Weight> ShortestPaths<W> {
+    fn new(n: usize, source: usize) -> ShortestPaths<W> {
+        let mut distances = vec![None; n];
+        distances[source] = Some(W::zero());
+
+        ShortestPaths {
+            source,
+            distances,
+            parents: vec![None; n],
+        }
+    }
+
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// `None` when `vertex` is unreachable.
+    pub fn distance(&self, vertex: usize) -> Option<W> {
+        *self.distances.get(vertex)?
+    }
+
+    pub fn parent(&self, vertex: usize) -> Option<usize> {
+        *self.parents.get(vertex)?
+    }
+
+    /// The vertices from the source to `vertex`, `None` when it is unreachable.
+    pub fn path(&self, vertex: usize) -> Option<Vec<usize>> {
+        self.distance(vertex)?;
+
+        let mut path = vec![vertex];
+        let mut current = vertex;
+        while let Some(parent) = self.parent(current) {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // records `distance` through `parent` when it is shorter
+    fn relax(&mut self, parent: usize, vertex: usize, distance: W) -> bool {
+        match self.distances[vertex] {
+            Some(current) if current <= distance => false,
+            _ => {
+                self.distances[vertex] = Some(distance);
+                self.parents[vertex] = Some(parent);
+                true
+            }
+        }
+    }
+}
+
+fn check_source<W, G>(graph: &G, source: usize) -> Result<(), CollectionError>
+where
+    G: WeightedGraph<W> + ?Sized,
+{
+    if source >= graph.n() {
+        return Err(OUT_OF_BOUND_ERROR);
+    }
+    Ok(())
+}
+
+/// O((n + m) log n) on an `IndexedBinaryHeap`, fails on a negative weight.
+pub fn dijkstra<W, G>(graph: &G, source: usize) -> Result<ShortestPaths<W>, CollectionError>
+where
+    W: Weight,
+    G: WeightedGraph<W> + ?Sized,
+{
+    check_source(graph, source)?;
+
+    let mut paths = ShortestPaths::new(graph.n(), source);
+    let mut heap = IndexedBinaryHeap::new();
+    let mut handles = vec![None; graph.n()];
+    handles[source] = Some(heap.insert((W::zero(), source)));
+
+    while let Some((distance, vertex)) = heap.remove_min() {
+        for (neighbour, weight) in graph.out_edges(vertex) {
+            if weight < W::zero() {
+                return Err(INVALID_ARGUMENT_ERROR);
+            }
+
+            let candidate = distance + weight;
+            if !paths.relax(vertex, neighbour, candidate) {
+                continue;
+            }
+
+            match handles[neighbour] {
+                Some(handle) if heap.contains(&handle) => {
+                    heap.decrease_key(&handle, (candidate, neighbour))?
+                }
+                _ => handles[neighbour] = Some(heap.insert((candidate, neighbour))),
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+/// O(n m), accepts negative weights but fails on a negative cycle reachable from `source`.
+pub fn bellman_ford<W, G>(graph: &G, source: usize) -> Result<ShortestPaths<W>, CollectionError>
+where
+    W: Weight,
+    G: WeightedGraph<W> + ?Sized,
+{
+    check_source(graph, source)?;
+
+    let mut paths = ShortestPaths::new(graph.n(), source);
+    // a shortest path has at most n - 1 edges, so one more improving round means a cycle
+    for _ in 0..graph.n() {
+        let mut is_relaxed = false;
+        for vertex in 0..graph.n() {
+            let distance = match paths.distances[vertex] {
+                Some(distance) => distance,
+                None => continue,
+            };
+            for (neighbour, weight) in graph.out_edges(vertex) {
+                if paths.relax(vertex, neighbour, distance + weight) {
+                    is_relaxed = true;
+                }
+            }
+        }
+
+        if !is_relaxed {
+            return Ok(paths);
+        }
+    }
+
+    Err(NEGATIVE_CYCLE_ERROR)
+}
+
+/// The distance and the vertices of a shortest path from `source` to `target`, `None` when
+/// it is unreachable. `heuristic` must not overestimate the distance to `target`, the
+/// search is fastest when it also never decreases by more than an edge weight.
+pub fn a_star<W, G, H>(
+    graph: &G,
+    source: usize,
+    target: usize,
+    mut heuristic: H,
+) -> Result<Option<(W, Vec<usize>)>, CollectionError>
+where
+    W: Weight,
+    G: WeightedGraph<W> + ?Sized,
+    H: FnMut(usize) -> W,
+{
+    check_source(graph, source)?;
+    check_source(graph, target)?;
+
+    let mut paths = ShortestPaths::new(graph.n(), source);
+    let mut estimates: Vec<Option<W>> = vec![None; graph.n()];
+    let mut heap = IndexedBinaryHeap::new();
+    let mut handles = vec![None; graph.n()];
+    handles[source] = Some(heap.insert((heuristic(source), source)));
+
+    while let Some((_, vertex)) = heap.remove_min() {
+        let distance = match paths.distance(vertex) {
+            Some(distance) => distance,
+            None => unreachable!(),
+        };
+        if vertex == target {
+            let path = paths.path(target).unwrap_or_default();
+            return Ok(Some((distance, path)));
+        }
+
+        for (neighbour, weight) in graph.out_edges(vertex) {
+            if weight < W::zero() {
+                return Err(INVALID_ARGUMENT_ERROR);
+            }
+
+            let candidate = distance + weight;
+            if !paths.relax(vertex, neighbour, candidate) {
+                continue;
+            }
+
+            let estimate = match estimates[neighbour] {
+                Some(estimate) => estimate,
+                None => {
+                    let estimate = heuristic(neighbour);
+                    estimates[neighbour] = Some(estimate);
+                    estimate
+                }
+            };
+            let key = (candidate + estimate, neighbour);
+
+            // a vertex already removed is reopened with its shorter distance
+            match handles[neighbour] {
+                Some(handle) if heap.contains(&handle) => heap.decrease_key(&handle, key)?,
+                _ => handles[neighbour] = Some(heap.insert(key)),
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// O(n + m) on an `ArrayDeque` for weights of zero or one only.
+pub fn zero_one_bfs<W, G>(graph: &G, source: usize) -> Result<ShortestPaths<W>, CollectionError>
+where
+    W: Weight,
+    G: WeightedGraph<W> + ?Sized,
+{
+    check_source(graph, source)?;
+
+    let mut paths = ShortestPaths::new(graph.n(), source);
+    let mut deque = ArrayDeque::new();
+    deque.add_last(source);
+
+    // a vertex may be queued twice, the deque keeps the distances sorted so the first
+    // removal is final
+    let mut is_done = vec![false; graph.n()];
+    while let Some(vertex) = deque.remove_first() {
+        if is_done[vertex] {
+            continue;
+        }
+        is_done[vertex] = true;
+
+        let distance = match paths.distance(vertex) {
+            Some(distance) => distance,
+            None => unreachable!(),
+        };
+        for (neighbour, weight) in graph.out_edges(vertex) {
+            let is_zero = weight == W::zero();
+            if !is_zero && weight != W::one() {
+                return Err(INVALID_ARGUMENT_ERROR);
+            }
+
+            if paths.relax(vertex, neighbour, distance + weight) {
+                if is_zero {
+                    deque.add_first(neighbour);
+                } else {
+                    deque.add_last(neighbour);
+                }
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{a_star, bellman_ford, dijkstra, zero_one_bfs};
+    use crate::graph::{WeightedAdjacencyLists, WeightedGraph};
+    use crate::{INVALID_ARGUMENT_ERROR, NEGATIVE_CYCLE_ERROR, OUT_OF_BOUND_ERROR};
+
+    fn weighted(n: usize, edges: &[(usize, usize, i64)]) -> WeightedAdjacencyLists<i64> {
+        let mut graph = WeightedAdjacencyLists::new(n);
+        for (i, j, weight) in edges.iter() {
+            graph.add_edge(*i, *j, *weight).unwrap();
+        }
+        graph
+    }
+
+    // a grid of `size` x `size` vertices, walls cost 9 to enter and other cells 1
+    fn grid(size: usize, walls: &[usize]) -> WeightedAdjacencyLists<i64> {
+        let mut graph = WeightedAdjacencyLists::new(size * size);
+        for vertex in 0..size * size {
+            let (row, column) = (vertex / size, vertex % size);
+            let mut neighbours = vec![];
+            if row > 0 {
+                neighbours.push(vertex - size);
+            }
+            if row + 1 < size {
+                neighbours.push(vertex + size);
+            }
+            if column > 0 {
+                neighbours.push(vertex - 1);
+            }
+            if column + 1 < size {
+                neighbours.push(vertex + 1);
+            }
+
+            for neighbour in neighbours {
+                let weight = if walls.contains(&neighbour) { 9 } else { 1 };
+                graph.add_edge(vertex, neighbour, weight).unwrap();
+            }
+        }
+        graph
+    }
+
+    #[test]
+    pub fn shortest_paths() {
+        let graph = weighted(
+            6,
+            &[
+                (0, 1, 7),
+                (0, 2, 9),
+                (0, 5, 14),
+                (1, 2, 10),
+                (1, 3, 15),
+                (2, 3, 11),
+                (2, 5, 2),
+                (3, 4, 6),
+                (5, 4, 9),
+            ],
+        );
+
+        let paths = dijkstra(&graph, 0).unwrap();
+        assert_eq!(paths.source(), 0);
+        assert_eq!(paths.distance(4), Some(20));
+        assert_eq!(paths.path(4), Some(vec![0, 2, 5, 4]));
+        assert_eq!(paths.distance(3), Some(20));
+        assert_eq!(paths.path(0), Some(vec![0]));
+
+        assert_eq!(bellman_ford(&graph, 0), Ok(paths.clone()));
+
+        let paths = dijkstra(&graph, 4).unwrap();
+        assert_eq!(paths.distance(0), None);
+        assert_eq!(paths.path(0), None);
+
+        assert_eq!(dijkstra(&graph, 6), Err(OUT_OF_BOUND_ERROR));
+    }
+
+    #[test]
+    pub fn negative_weights() {
+        let mut graph = weighted(4, &[(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 2)]);
+
+        let paths = bellman_ford(&graph, 0).unwrap();
+        assert_eq!(paths.distance(1), Some(2));
+        assert_eq!(paths.path(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(dijkstra(&graph, 0), Err(INVALID_ARGUMENT_ERROR));
+
+        graph.add_edge(3, 2, -1).unwrap();
+        assert_eq!(bellman_ford(&graph, 0), Err(NEGATIVE_CYCLE_ERROR));
+
+        // only the cycles reachable from the source count
+        let graph = weighted(3, &[(1, 2, -1), (2, 1, -1)]);
+        assert!(bellman_ford(&graph, 0).is_ok());
+    }
+
+    #[test]
+    pub fn heuristic_search() {
+        let size = 8;
+        let walls = [10, 18, 26, 34, 42, 43, 44];
+        let graph = grid(size, &walls);
+        let target = size * size - 1;
+
+        let manhattan = |vertex: usize| {
+            let (row, column) = (vertex / size, vertex % size);
+            ((size - 1 - row) + (size - 1 - column)) as i64
+        };
+        let (distance, path) = a_star(&graph, 0, target, manhattan).unwrap().unwrap();
+
+        let paths = dijkstra(&graph, 0).unwrap();
+        assert_eq!(Some(distance), paths.distance(target));
+        assert_eq!(path.first(), Some(&0));
+        assert_eq!(path.last(), Some(&target));
+        let cost: i64 = path
+            .windows(2)
+            .map(|edge| *graph.weight(edge[0], edge[1]).unwrap())
+            .sum();
+        assert_eq!(cost, distance);
+
+        // without a heuristic it is Dijkstra
+        let (distance, _) = a_star(&graph, target, 0, |_| 0).unwrap().unwrap();
+        assert_eq!(
+            Some(distance),
+            dijkstra(&graph, target).unwrap().distance(0)
+        );
+
+        let graph = weighted(3, &[(0, 1, 1)]);
+        assert_eq!(a_star(&graph, 0, 2, |_| 0), Ok(None));
+    }
+
+    #[test]
+    pub fn zero_one() {
+        let size = 6;
+        let mut graph = grid(size, &[]);
+        // free moves along the diagonal
+        for vertex in 0..size - 1 {
+            let diagonal = vertex * (size + 1);
+            graph.add_edge(diagonal, diagonal + 1, 0).unwrap();
+            graph
+                .add_edge(diagonal + 1, diagonal + 1 + size, 0)
+                .unwrap();
+        }
+
+        let paths = zero_one_bfs(&graph, 0).unwrap();
+        assert_eq!(paths, dijkstra(&graph, 0).unwrap());
+        assert_eq!(paths.distance(size * size - 1), Some(0));
+
+        graph.add_edge(0, 1, 2).unwrap();
+        assert_eq!(zero_one_bfs(&graph, 0), Err(INVALID_ARGUMENT_ERROR));
+    }
+}
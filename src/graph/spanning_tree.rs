@@ -0,0 +1,141 @@
+//! Minimum spanning forests, reading every edge `(i, j)` as an undirected edge between `i` and
+//! `j`. Edges are returned as `(i, j, weight)` and connect one tree per connected component.
+
+use super::{Weight, WeightedGraph};
+use crate::indexed_binary_heap::IndexedBinaryHeap;
+use crate::sorting::merge_sort_slice_by_key;
+use crate::union_find::UnionFind;
+use crate::{AddressablePriorityQueue, PriorityQueue};
+
+/// O(m log m), by adding the lightest edges that join two trees.
+pub fn kruskal<W, G>(graph: &G) -> Vec<(usize, usize, W)>
+where
+    W: Weight,
+    G: WeightedGraph<W> + ?Sized,
+{
+    let mut edges = vec![];
+    for vertex in 0..graph.n() {
+        for (neighbour, weight) in graph.out_edges(vertex) {
+            if vertex != neighbour {
+                edges.push((vertex, neighbour, weight));
+            }
+        }
+    }
+    merge_sort_slice_by_key(&mut edges, |edge| edge.2);
+
+    let mut forest = UnionFind::with_size(graph.n());
+    let mut tree = vec![];
+    for (i, j, weight) in edges {
+        if forest.union(i, j) == Ok(true) {
+            tree.push((i, j, weight));
+        }
+    }
+    tree
+}
+
+/// O(m log n) on an `IndexedBinaryHeap`, by growing each tree with its lightest outgoing edge.
+pub fn prim<W, G>(graph: &G) -> Vec<(usize, usize, W)>
+where
+    W: Weight,
+    G: WeightedGraph<W> + ?Sized,
+{
+    let mut neighbours = vec![vec![]; graph.n()];
+    for vertex in 0..graph.n() {
+        for (neighbour, weight) in graph.out_edges(vertex) {
+            neighbours[vertex].push((neighbour, weight));
+            neighbours[neighbour].push((vertex, weight));
+        }
+    }
+
+    let mut is_in_tree = vec![false; graph.n()];
+    // the lightest edge from the trees to each vertex
+    let mut lightest: Vec<Option<(W, usize)>> = vec![None; graph.n()];
+    let mut handles = vec![None; graph.n()];
+    let mut heap = IndexedBinaryHeap::new();
+    let mut tree = vec![];
+
+    for root in 0..graph.n() {
+        if is_in_tree[root] {
+            continue;
+        }
+        heap.insert((W::zero(), root));
+
+        while let Some((weight, vertex)) = heap.remove_min() {
+            is_in_tree[vertex] = true;
+            if let Some((_, parent)) = lightest[vertex] {
+                tree.push((parent, vertex, weight));
+            }
+
+            for (neighbour, weight) in neighbours[vertex].iter().copied() {
+                if is_in_tree[neighbour] {
+                    continue;
+                }
+                match lightest[neighbour] {
+                    Some((current, _)) if current <= weight => continue,
+                    _ => lightest[neighbour] = Some((weight, vertex)),
+                }
+
+                match handles[neighbour] {
+                    Some(handle) if heap.contains(&handle) => {
+                        match heap.decrease_key(&handle, (weight, neighbour)) {
+                            Ok(()) => (),
+                            _ => unreachable!(),
+                        }
+                    }
+                    _ => handles[neighbour] = Some(heap.insert((weight, neighbour))),
+                }
+            }
+        }
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kruskal, prim};
+    use crate::graph::{WeightedAdjacencyLists, WeightedGraph};
+
+    fn total(tree: &[(usize, usize, i32)]) -> i32 {
+        tree.iter().map(|edge| edge.2).sum()
+    }
+
+    #[test]
+    pub fn spanning_tree() {
+        //   0 --4-- 1 --8-- 2        5 --3-- 6
+        //   |     / |       |
+        //   1   2   6       3
+        //   | /     |       |
+        //   3 --9-- 4 --3-- +
+        let mut graph = WeightedAdjacencyLists::new(7);
+        for (i, j, weight) in [
+            (0, 1, 4),
+            (0, 3, 1),
+            (1, 3, 2),
+            (1, 2, 8),
+            (1, 4, 6),
+            (3, 4, 9),
+            (2, 4, 3),
+            (6, 5, 3),
+            (5, 5, -7),
+        ]
+        .iter()
+        {
+            graph.add_edge(*i, *j, *weight).unwrap();
+        }
+
+        let tree = kruskal(&graph);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(total(&tree), 1 + 2 + 6 + 3 + 3);
+
+        let tree = prim(&graph);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(total(&tree), 1 + 2 + 6 + 3 + 3);
+        assert!(tree.contains(&(3, 1, 2)));
+
+        // the direction of an edge does not matter
+        graph.remove_edge(1, 4);
+        graph.add_edge(4, 1, 6).unwrap();
+        assert_eq!(total(&kruskal(&graph)), 15);
+        assert_eq!(total(&prim(&graph)), 15);
+    }
+}
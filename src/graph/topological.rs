@@ -0,0 +1,177 @@
+use super::Graph;
+use crate::array_queue::ArrayQueue;
+use crate::array_stack::ArrayStack;
+use crate::{List, Queue, Stack};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Unvisited,
+    // on the current depth-first path
+    Active,
+    Finished,
+}
+
+/// Kahn's algorithm in O(n + m), every edge `(i, j)` has `i` before `j`. `None` when the graph
+/// has a cycle.
+pub fn topological_sort<G: Graph + ?Sized>(graph: &G) -> Option<Vec<usize>> {
+    let mut in_degrees = vec![0; graph.n()];
+    for vertex in 0..graph.n() {
+        for neighbour in graph.out_edges(vertex) {
+            in_degrees[neighbour] += 1;
+        }
+    }
+
+    let mut queue = ArrayQueue::new();
+    for (vertex, in_degree) in in_degrees.iter().enumerate() {
+        if *in_degree == 0 {
+            queue.enqueue(vertex);
+        }
+    }
+
+    let mut order = Vec::with_capacity(graph.n());
+    while let Some(vertex) = queue.dequeue() {
+        order.push(vertex);
+        for neighbour in graph.out_edges(vertex) {
+            in_degrees[neighbour] -= 1;
+            if in_degrees[neighbour] == 0 {
+                queue.enqueue(neighbour);
+            }
+        }
+    }
+
+    if order.len() < graph.n() {
+        return None;
+    }
+    Some(order)
+}
+
+/// The reverse depth-first postorder in O(n + m). `None` when the graph has a cycle.
+pub fn topological_sort_dfs<G: Graph + ?Sized>(graph: &G) -> Option<Vec<usize>> {
+    let mut order = postorder(graph).ok()?;
+    order.reverse();
+    Some(order)
+}
+
+/// The vertices of a cycle in O(n + m), each with an edge to the next and the last with an
+/// edge to the first.
+pub fn find_cycle<G: Graph + ?Sized>(graph: &G) -> Option<Vec<usize>> {
+    postorder(graph).err()
+}
+
+pub fn has_cycle<G: Graph + ?Sized>(graph: &G) -> bool {
+    find_cycle(graph).is_some()
+}
+
+// the postorder of an iterative depth-first search over every vertex, or the first cycle
+// closed by an edge back to an active vertex
+fn postorder<G: Graph + ?Sized>(graph: &G) -> Result<Vec<usize>, Vec<usize>> {
+    let mut colors = vec![Color::Unvisited; graph.n()];
+    let mut parents = vec![None; graph.n()];
+    let mut order = Vec::with_capacity(graph.n());
+    // the vertices of the path with their out edges and the index of the next one
+    let mut path = ArrayStack::new();
+
+    for root in 0..graph.n() {
+        if colors[root] != Color::Unvisited {
+            continue;
+        }
+        colors[root] = Color::Active;
+        path.push((root, graph.out_edges(root), 0));
+
+        while let Some((vertex, edges, index)) = path.pop() {
+            let neighbour = match edges.get(index) {
+                Some(neighbour) => *neighbour,
+                None => {
+                    colors[vertex] = Color::Finished;
+                    order.push(vertex);
+                    continue;
+                }
+            };
+            path.push((vertex, edges, index + 1));
+
+            match colors[neighbour] {
+                Color::Unvisited => {
+                    colors[neighbour] = Color::Active;
+                    parents[neighbour] = Some(vertex);
+                    path.push((neighbour, graph.out_edges(neighbour), 0));
+                }
+                Color::Active => {
+                    let mut cycle = vec![vertex];
+                    let mut current = vertex;
+                    while current != neighbour {
+                        current = parents[current].unwrap();
+                        cycle.push(current);
+                    }
+                    cycle.reverse();
+                    return Err(cycle);
+                }
+                Color::Finished => (),
+            }
+        }
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_cycle, has_cycle, topological_sort, topological_sort_dfs};
+    use crate::graph::{AdjacencyLists, AdjacencyMatrix, Graph};
+
+    fn is_topological<G: Graph>(graph: &G, order: &[usize]) -> bool {
+        let mut positions = vec![None; graph.n()];
+        for (position, vertex) in order.iter().enumerate() {
+            positions[*vertex] = Some(position);
+        }
+
+        positions.iter().all(|position| position.is_some())
+            && (0..graph.n()).all(|i| {
+                graph
+                    .out_edges(i)
+                    .into_iter()
+                    .all(|j| positions[i] < positions[j])
+            })
+    }
+
+    #[test]
+    pub fn topological() {
+        let mut graph = AdjacencyMatrix::new(6);
+        for (i, j) in [(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)].iter() {
+            graph.add_edge(*i, *j).unwrap();
+        }
+
+        let order = topological_sort(&graph).unwrap();
+        assert!(is_topological(&graph, &order));
+        let order = topological_sort_dfs(&graph).unwrap();
+        assert!(is_topological(&graph, &order));
+
+        graph.add_edge(1, 5).unwrap();
+        assert_eq!(topological_sort(&graph), None);
+        assert_eq!(topological_sort_dfs(&graph), None);
+
+        // deep enough to overflow a recursive search
+        let mut graph = AdjacencyLists::new(100_000);
+        for i in 1..100_000 {
+            graph.add_edge(i, i - 1).unwrap();
+        }
+        let order = topological_sort_dfs(&graph).unwrap();
+        assert_eq!(order[0], 99_999);
+        assert_eq!(order[99_999], 0);
+    }
+
+    #[test]
+    pub fn cycle() {
+        let mut graph = AdjacencyLists::new(5);
+        for (i, j) in [(0, 1), (1, 2), (2, 3), (0, 3)].iter() {
+            graph.add_edge(*i, *j).unwrap();
+        }
+        assert!(!has_cycle(&graph));
+
+        graph.add_edge(3, 1).unwrap();
+        assert_eq!(find_cycle(&graph), Some(vec![1, 2, 3]));
+
+        graph.remove_edge(3, 1);
+        graph.add_edge(4, 4).unwrap();
+        assert_eq!(find_cycle(&graph), Some(vec![4]));
+    }
+}
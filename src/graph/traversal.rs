@@ -0,0 +1,256 @@
+use super::Graph;
+use crate::array_queue::ArrayQueue;
+use crate::array_stack::ArrayStack;
+use crate::{Queue, Stack};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// A vertex reached by a traversal, with the vertex it was reached from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visit<V> {
+    pub vertex: V,
+    pub parent: Option<V>,
+    /// The number of edges from the start, in the traversal tree.
+    pub depth: usize,
+}
+
+/// Breadth-first traversal of the vertices reachable from `start`, in order of distance.
+pub fn bfs<G: Graph + ?Sized>(
+    graph: &G,
+    start: usize,
+) -> Bfs<usize, impl FnMut(&usize) -> ArrayStack<usize> + '_> {
+    let start = if start < graph.n() { Some(start) } else { None };
+    Bfs::new(start, move |vertex: &usize| graph.out_edges(*vertex))
+}
+
+/// Breadth-first traversal of an implicit graph, `neighbours` is only called on the visited
+/// vertices.
+pub fn bfs_with<V, F, I>(start: V, neighbours: F) -> Bfs<V, F>
+where
+    V: Clone + Eq + Hash,
+    F: FnMut(&V) -> I,
+    I: IntoIterator<Item = V>,
+{
+    Bfs::new(Some(start), neighbours)
+}
+
+/// Depth-first traversal of the vertices reachable from `start`, in preorder.
+pub fn dfs<G: Graph + ?Sized>(
+    graph: &G,
+    start: usize,
+) -> Dfs<usize, impl FnMut(&usize) -> ArrayStack<usize> + '_> {
+    let start = if start < graph.n() { Some(start) } else { None };
+    Dfs::new(start, move |vertex: &usize| graph.out_edges(*vertex))
+}
+
+/// Depth-first traversal of an implicit graph, `neighbours` is only called on the visited
+/// vertices.
+pub fn dfs_with<V, F, I>(start: V, neighbours: F) -> Dfs<V, F>
+where
+    V: Clone + Eq + Hash,
+    F: FnMut(&V) -> I,
+    I: IntoIterator<Item = V>,
+{
+    Dfs::new(Some(start), neighbours)
+}
+
+#[derive(Debug)]
+pub struct Bfs<V, F> {
+    queue: ArrayQueue<Visit<V>>,
+    // the vertices ever added to the queue
+    discovered: HashSet<V>,
+    neighbours: F,
+}
+
+impl<V: Clone + Eq + Hash, F> Bfs<V, F> {
+    fn new(start: Option<V>, neighbours: F) -> Bfs<V, F> {
+        let mut bfs = Bfs {
+            queue: ArrayQueue::new(),
+            discovered: HashSet::new(),
+            neighbours,
+        };
+
+        if let Some(start) = start {
+            bfs.discovered.insert(start.clone());
+            bfs.queue.enqueue(Visit {
+                vertex: start,
+                parent: None,
+                depth: 0,
+            });
+        }
+        bfs
+    }
+}
+
+impl<V, F, I> Iterator for Bfs<V, F>
+where
+    V: Clone + Eq + Hash,
+    F: FnMut(&V) -> I,
+    I: IntoIterator<Item = V>,
+{
+    type Item = Visit<V>;
+
+    fn next(&mut self) -> Option<Visit<V>> {
+        let visit = self.queue.dequeue()?;
+
+        for neighbour in (self.neighbours)(&visit.vertex) {
+            if self.discovered.insert(neighbour.clone()) {
+                self.queue.enqueue(Visit {
+                    vertex: neighbour,
+                    parent: Some(visit.vertex.clone()),
+                    depth: visit.depth + 1,
+                });
+            }
+        }
+
+        Some(visit)
+    }
+}
+
+#[derive(Debug)]
+pub struct Dfs<V, F> {
+    // may hold a vertex several times, only its last push is visited
+    stack: ArrayStack<Visit<V>>,
+    visited: HashSet<V>,
+    neighbours: F,
+}
+
+impl<V: Clone + Eq + Hash, F> Dfs<V, F> {
+    fn new(start: Option<V>, neighbours: F) -> Dfs<V, F> {
+        let mut stack = ArrayStack::new();
+        if let Some(start) = start {
+            stack.push(Visit {
+                vertex: start,
+                parent: None,
+                depth: 0,
+            });
+        }
+
+        Dfs {
+            stack,
+            visited: HashSet::new(),
+            neighbours,
+        }
+    }
+}
+
+impl<V, F, I> Iterator for Dfs<V, F>
+where
+    V: Clone + Eq + Hash,
+    F: FnMut(&V) -> I,
+    I: IntoIterator<Item = V>,
+{
+    type Item = Visit<V>;
+
+    fn next(&mut self) -> Option<Visit<V>> {
+        loop {
+            let visit = self.stack.pop()?;
+            if !self.visited.insert(visit.vertex.clone()) {
+                continue;
+            }
+
+            // pushed in reverse, so that the neighbours are visited in their order
+            let neighbours: Vec<V> = (self.neighbours)(&visit.vertex)
+                .into_iter()
+                .filter(|neighbour| !self.visited.contains(neighbour))
+                .collect();
+            for neighbour in neighbours.into_iter().rev() {
+                self.stack.push(Visit {
+                    vertex: neighbour,
+                    parent: Some(visit.vertex.clone()),
+                    depth: visit.depth + 1,
+                });
+            }
+
+            return Some(visit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bfs, bfs_with, dfs, dfs_with, Visit};
+    use crate::graph::{AdjacencyLists, Graph};
+
+    //   0 -> 1 -> 3
+    //   |    ^    |
+    //   v    |    v
+    //   2 ---+    4    5
+    fn graph() -> AdjacencyLists {
+        let mut graph = AdjacencyLists::new(6);
+        for (i, j) in [(0, 1), (0, 2), (1, 3), (2, 1), (3, 4), (4, 0)].iter() {
+            graph.add_edge(*i, *j).unwrap();
+        }
+        graph
+    }
+
+    fn visit(vertex: usize, parent: Option<usize>, depth: usize) -> Visit<usize> {
+        Visit {
+            vertex,
+            parent,
+            depth,
+        }
+    }
+
+    #[test]
+    pub fn breadth_first() {
+        let graph = graph();
+
+        let visits: Vec<Visit<usize>> = bfs(&graph, 0).collect();
+        assert_eq!(
+            visits,
+            vec![
+                visit(0, None, 0),
+                visit(1, Some(0), 1),
+                visit(2, Some(0), 1),
+                visit(3, Some(1), 2),
+                visit(4, Some(3), 3),
+            ]
+        );
+
+        assert_eq!(bfs(&graph, 5).count(), 1);
+        assert_eq!(bfs(&graph, 6).count(), 0);
+    }
+
+    #[test]
+    pub fn depth_first() {
+        let graph = graph();
+
+        let visits: Vec<Visit<usize>> = dfs(&graph, 0).collect();
+        assert_eq!(
+            visits,
+            vec![
+                visit(0, None, 0),
+                visit(1, Some(0), 1),
+                visit(3, Some(1), 2),
+                visit(4, Some(3), 3),
+                visit(2, Some(0), 1),
+            ]
+        );
+
+        assert_eq!(dfs(&graph, 6).count(), 0);
+    }
+
+    #[test]
+    pub fn implicit() {
+        // the shortest ways to reach 10 from 1 by doubling or adding one
+        let neighbours = |x: &u64| vec![x * 2, x + 1].into_iter().filter(|y| *y <= 10);
+
+        let ten = bfs_with(1u64, neighbours)
+            .find(|visit| visit.vertex == 10)
+            .unwrap();
+        assert_eq!(ten.depth, 4);
+        assert_eq!(ten.parent, Some(5));
+
+        let vertices: Vec<u64> = dfs_with(1u64, neighbours)
+            .map(|visit| visit.vertex)
+            .collect();
+        assert_eq!(vertices, vec![1, 2, 4, 8, 9, 10, 5, 6, 7, 3]);
+
+        // an infinite state space is explored lazily
+        let far = bfs_with(0i64, |x: &i64| vec![x - 1, x + 1])
+            .find(|visit| visit.depth == 100)
+            .unwrap();
+        assert_eq!(far.vertex.abs(), 100);
+    }
+}
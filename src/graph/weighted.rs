@@ -0,0 +1,143 @@
+use crate::array_stack::ArrayStack;
+use crate::CollectionError;
+use crate::OUT_OF_BOUND_ERROR;
+use crate::{List, Stack};
+use std::ops::{Add, Sub};
+
+/// An edge weight. Floating point weights need a wrapper with a total order.
+pub trait Weight: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A directed graph over the vertices `0..n` with a weight on every edge.
+pub trait WeightedGraph<W> {
+    fn n(&self) -> usize;
+    /// Adds the edge `(i, j)` or replaces its weight, fails when a vertex is out of bound.
+    fn add_edge(&mut self, i: usize, j: usize, weight: W) -> Result<(), CollectionError>;
+    fn remove_edge(&mut self, i: usize, j: usize) -> Option<W>;
+    fn weight(&self, i: usize, j: usize) -> Option<&W>;
+    /// The vertices `j` and weights of the edges `(i, j)`.
+    fn out_edges(&self, i: usize) -> ArrayStack<(usize, W)>;
+    /// The vertices `j` and weights of the edges `(j, i)`.
+    fn in_edges(&self, i: usize) -> ArrayStack<(usize, W)>;
+}
+
+/// O(deg(i)) edge operations and `out_edges`, O(n + m) `in_edges`, O(n + m) space.
+#[derive(Debug)]
+pub struct WeightedAdjacencyLists<W> {
+    adjacency: Vec<ArrayStack<(usize, W)>>,
+}
+
+impl<W> WeightedAdjacencyLists<W> {
+    pub fn new(n: usize) -> WeightedAdjacencyLists<W> {
+        WeightedAdjacencyLists {
+            adjacency: (0..n).map(|_| ArrayStack::new()).collect(),
+        }
+    }
+
+    fn position(&self, i: usize, j: usize) -> Option<usize> {
+        let edges = self.adjacency.get(i)?;
+        edges.iter().position(|edge| edge.0 == j)
+    }
+}
+
+impl<W: Clone> WeightedGraph<W> for WeightedAdjacencyLists<W> {
+    fn n(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn add_edge(&mut self, i: usize, j: usize, weight: W) -> Result<(), CollectionError> {
+        if i >= self.n() || j >= self.n() {
+            return Err(OUT_OF_BOUND_ERROR);
+        }
+
+        match self.position(i, j) {
+            Some(position) => self.adjacency[i].set(position, (j, weight)),
+            None => {
+                self.adjacency[i].push((j, weight));
+                Ok(())
+            }
+        }
+    }
+
+    fn remove_edge(&mut self, i: usize, j: usize) -> Option<W> {
+        let position = self.position(i, j)?;
+        let (_, weight) = self.adjacency[i].remove(position)?;
+        Some(weight)
+    }
+
+    fn weight(&self, i: usize, j: usize) -> Option<&W> {
+        let position = self.position(i, j)?;
+        let (_, weight) = self.adjacency[i].get(position)?;
+        Some(weight)
+    }
+
+    fn out_edges(&self, i: usize) -> ArrayStack<(usize, W)> {
+        let mut edges = ArrayStack::new();
+        if let Some(out_edges) = self.adjacency.get(i) {
+            for edge in out_edges.iter() {
+                edges.push(edge.clone());
+            }
+        }
+        edges
+    }
+
+    fn in_edges(&self, i: usize) -> ArrayStack<(usize, W)> {
+        let mut edges = ArrayStack::new();
+        for j in 0..self.n() {
+            if let Some(weight) = self.weight(j, i) {
+                edges.push((j, weight.clone()));
+            }
+        }
+        edges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WeightedAdjacencyLists, WeightedGraph};
+    use crate::OUT_OF_BOUND_ERROR;
+
+    #[test]
+    pub fn weighted_graph() {
+        let mut graph = WeightedAdjacencyLists::new(3);
+        assert_eq!(graph.n(), 3);
+
+        assert_eq!(graph.add_edge(0, 1, 5), Ok(()));
+        assert_eq!(graph.add_edge(0, 2, 7), Ok(()));
+        assert_eq!(graph.add_edge(2, 1, -1), Ok(()));
+        assert_eq!(graph.add_edge(0, 1, 4), Ok(()));
+        assert_eq!(graph.add_edge(0, 3, 1), Err(OUT_OF_BOUND_ERROR));
+
+        assert_eq!(graph.weight(0, 1), Some(&4));
+        assert_eq!(graph.weight(1, 0), None);
+
+        let out_edges: Vec<(usize, i32)> = graph.out_edges(0).into_iter().collect();
+        assert_eq!(out_edges, vec![(1, 4), (2, 7)]);
+        let in_edges: Vec<(usize, i32)> = graph.in_edges(1).into_iter().collect();
+        assert_eq!(in_edges, vec![(0, 4), (2, -1)]);
+
+        assert_eq!(graph.remove_edge(0, 1), Some(4));
+        assert_eq!(graph.remove_edge(0, 1), None);
+        assert_eq!(graph.out_edges(0).iter().count(), 1);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::backend::{Array, Entry};
+use crate::owner::unique_id;
+use crate::CollectionError;
+use crate::{AddressablePriorityQueue, PriorityQueue};
+use crate::{INVALID_HANDLE_ERROR, INVALID_KEY_ERROR};
+
+const DEFAULT_BACKEND_SIZE: usize = 2;
+const SIZE_UP_MULTIPLIER_NUMBER: usize = 2;
+const SIZE_DOWN_THRESHOLD: usize = 3;
+const SIZE_DOWN_DIVISION_NUMBER: usize = 2;
+
+/// Refers to an item of an `IndexedBinaryHeap`, invalidated when the item is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    // the id of the heap
+    heap: u64,
+    index: usize,
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct Slot<T> {
+    item: Option<T>,
+    position: usize,
+    generation: u64,
+}
+
+/// A binary heap that keeps the heap position of every item, so that any item can be
+/// updated or removed in O(log n). Handles of another heap are rejected.
+#[derive(Debug)]
+pub struct IndexedBinaryHeap<T> {
+    id: u64,
+    backend: Array<usize>,
+    size: usize,
+    slots: Vec<Slot<T>>,
+    free_slots: Vec<usize>,
+}
+
+impl<T: Ord> IndexedBinaryHeap<T> {
+    pub fn new() -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap {
+            id: unique_id(),
+            backend: Array::new(DEFAULT_BACKEND_SIZE),
+            size: 0,
+            slots: vec![],
+            free_slots: vec![],
+        }
+    }
+
+    fn slot_index(&self, position: usize) -> usize {
+        match self.backend.get(position) {
+            Some(Entry::Item(index)) => *index,
+            _ => unreachable!(),
+        }
+    }
+
+    fn item(&self, position: usize) -> &T {
+        match &self.slots[self.slot_index(position)].item {
+            Some(item) => item,
+            None => unreachable!(),
+        }
+    }
+
+    pub fn get(&self, handle: &Handle) -> Option<&T> {
+        let position = self.position(handle)?;
+        Some(self.item(position))
+    }
+
+    fn handle(&self, index: usize) -> Handle {
+        Handle {
+            heap: self.id,
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    fn position(&self, handle: &Handle) -> Option<usize> {
+        if handle.heap != self.id {
+            return None;
+        }
+        match self.slots.get(handle.index) {
+            Some(slot) if slot.generation == handle.generation && slot.item.is_some() => {
+                Some(slot.position)
+            }
+            _ => None,
+        }
+    }
+
+    fn place(&mut self, position: usize, index: usize) {
+        self.slots[index].position = position;
+        match self.backend.set(position, index) {
+            Ok(()) => (),
+            _ => unreachable!(),
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.backend.swap(a, b);
+        let index = self.slot_index(a);
+        self.slots[index].position = a;
+        let index = self.slot_index(b);
+        self.slots[index].position = b;
+    }
+
+    fn bubble_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.item(position) >= self.item(parent) {
+                break;
+            }
+            self.swap(position, parent);
+            position = parent;
+        }
+    }
+
+    fn trickle_down(&mut self, mut position: usize) {
+        loop {
+            let left = 2 * position + 1;
+            let right = left + 1;
+
+            let mut smallest = position;
+            if left < self.size && self.item(left) < self.item(smallest) {
+                smallest = left;
+            }
+            if right < self.size && self.item(right) < self.item(smallest) {
+                smallest = right;
+            }
+            if smallest == position {
+                break;
+            }
+
+            self.swap(position, smallest);
+            position = smallest;
+        }
+    }
+
+    fn replace(&mut self, position: usize, x: T) {
+        let index = self.slot_index(position);
+        self.slots[index].item = Some(x);
+    }
+}
+
+impl<T: Ord> Default for IndexedBinaryHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for IndexedBinaryHeap<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, x: T) {
+        self.insert(x);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        if self.size == 0 {
+            return None;
+        }
+
+        Some(self.item(0))
+    }
+
+    fn remove_min(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+
+        let handle = self.handle(self.slot_index(0));
+        self.remove(&handle)
+    }
+}
+
+impl<T: Ord> AddressablePriorityQueue<T> for IndexedBinaryHeap<T> {
+    type Handle = Handle;
+
+    fn insert(&mut self, x: T) -> Handle {
+        if self.is_size_up_required() {
+            self.size_up();
+        }
+
+        let index = match self.free_slots.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot {
+                    item: None,
+                    position: 0,
+                    generation: 0,
+                });
+                self.slots.len() - 1
+            }
+        };
+        self.slots[index].item = Some(x);
+
+        self.place(self.size, index);
+        self.size += 1;
+        self.bubble_up(self.size - 1);
+
+        self.handle(index)
+    }
+
+    fn contains(&self, handle: &Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    fn decrease_key(&mut self, handle: &Handle, x: T) -> Result<(), CollectionError> {
+        let position = self.position(handle).ok_or(INVALID_HANDLE_ERROR)?;
+        if x > *self.item(position) {
+            return Err(INVALID_KEY_ERROR);
+        }
+
+        self.replace(position, x);
+        self.bubble_up(position);
+        Ok(())
+    }
+
+    fn increase_key(&mut self, handle: &Handle, x: T) -> Result<(), CollectionError> {
+        let position = self.position(handle).ok_or(INVALID_HANDLE_ERROR)?;
+        if x < *self.item(position) {
+            return Err(INVALID_KEY_ERROR);
+        }
+
+        self.replace(position, x);
+        self.trickle_down(position);
+        Ok(())
+    }
+
+    fn remove(&mut self, handle: &Handle) -> Option<T> {
+        let position = self.position(handle)?;
+
+        self.size -= 1;
+        self.swap(position, self.size);
+        match self.backend.remove(self.size) {
+            Some(Entry::Item(_)) => (),
+            _ => unreachable!(),
+        }
+
+        if position < self.size {
+            self.bubble_up(position);
+            self.trickle_down(position);
+        }
+
+        let slot = &mut self.slots[handle.index];
+        slot.generation += 1;
+        let item = slot.item.take();
+        self.free_slots.push(handle.index);
+
+        if self.is_size_down_required() {
+            self.size_down();
+        }
+
+        item
+    }
+}
+
+impl<T> IndexedBinaryHeap<T> {
+    fn backend_len(&self) -> usize {
+        self.backend.len()
+    }
+
+    fn is_size_up_required(&self) -> bool {
+        self.size == self.backend_len()
+    }
+
+    fn is_size_down_required(&self) -> bool {
+        self.size * SIZE_DOWN_THRESHOLD < self.backend_len()
+            && self.backend_len() > DEFAULT_BACKEND_SIZE
+    }
+
+    fn size_up(&mut self) {
+        self.backend
+            .resize(self.backend_len() * SIZE_UP_MULTIPLIER_NUMBER, 0, self.size);
+    }
+
+    fn size_down(&mut self) {
+        self.backend
+            .resize(self.backend_len() / SIZE_DOWN_DIVISION_NUMBER, 0, self.size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexedBinaryHeap;
+    use crate::INVALID_HANDLE_ERROR;
+    use crate::{AddressablePriorityQueue, PriorityQueue};
+
+    #[test]
+    pub fn priority_queue() {
+        crate::tests::priority_queue(IndexedBinaryHeap::new());
+    }
+
+    #[test]
+    pub fn addressable() {
+        crate::tests::addressable(IndexedBinaryHeap::new());
+    }
+
+    #[test]
+    pub fn foreign_handle() {
+        crate::tests::foreign_handle(IndexedBinaryHeap::new(), IndexedBinaryHeap::new());
+    }
+
+    #[test]
+    pub fn slots() {
+        let mut heap = IndexedBinaryHeap::new();
+        let mut other = IndexedBinaryHeap::new();
+        let a = heap.insert(20);
+        let b = heap.insert(10);
+        other.insert(30);
+
+        assert_eq!(heap.get(&a), Some(&20));
+        assert_eq!(other.get(&a), None);
+
+        // the freed slot is reused under another generation
+        assert_eq!(heap.remove(&a), Some(20));
+        let c = heap.insert(30);
+        assert_ne!(a, c);
+        assert_eq!(heap.get(&a), None);
+        assert!(!heap.contains(&a));
+        assert_eq!(heap.decrease_key(&a, 0), Err(INVALID_HANDLE_ERROR));
+        assert_eq!(heap.get(&c), Some(&30));
+
+        assert_eq!(heap.increase_key(&b, 40), Ok(()));
+        assert_eq!(heap.get(&b), Some(&40));
+        assert_eq!(heap.remove_min(), Some(30));
+        assert_eq!(heap.get(&c), None);
+        assert_eq!(heap.remove_min(), Some(40));
+        assert_eq!(heap.size(), 0);
+    }
+}
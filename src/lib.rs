@@ -1,10 +1,34 @@
+use std::borrow::Borrow;
 use std::error::Error;
 use std::fmt;
+use std::hash::Hash;
+use std::ops::{Bound, RangeBounds};
 
 pub mod array_deque;
 pub mod array_queue;
 pub mod array_stack;
+pub mod avl_tree;
+pub mod b_tree;
 mod backend;
+pub mod binary_heap;
+mod binary_search_tree;
+pub mod binary_trie;
+pub mod chained_hash_table;
+pub mod fibonacci_heap;
+pub mod graph;
+pub mod indexed_binary_heap;
+pub mod meldable_heap;
+pub mod min_max_heap;
+mod owner;
+pub mod pairing_heap;
+mod random;
+pub mod red_black_tree;
+pub mod selection;
+pub mod sorting;
+pub mod union_find;
+pub mod van_emde_boas_tree;
+pub mod x_fast_trie;
+pub mod y_fast_trie;
 
 pub trait Queue<T> {
     fn enqueue(&mut self, x: T);
@@ -23,29 +47,281 @@ pub trait Deque<T> {
     fn remove_last(&mut self) -> Option<T>;
 }
 
+pub trait PriorityQueue<T> {
+    fn size(&self) -> usize;
+    fn add(&mut self, x: T);
+    fn peek(&self) -> Option<&T>;
+    fn remove_min(&mut self) -> Option<T>;
+}
+
+/// A priority queue whose items can be reached through the handles returned on insertion.
+pub trait AddressablePriorityQueue<T>: PriorityQueue<T> {
+    type Handle;
+
+    fn insert(&mut self, x: T) -> Self::Handle;
+    fn contains(&self, handle: &Self::Handle) -> bool;
+    /// Replaces the item of `handle` with the smaller or equal `x`.
+    fn decrease_key(&mut self, handle: &Self::Handle, x: T) -> Result<(), CollectionError>;
+    /// Replaces the item of `handle` with the greater or equal `x`.
+    fn increase_key(&mut self, handle: &Self::Handle, x: T) -> Result<(), CollectionError>;
+    fn remove(&mut self, handle: &Self::Handle) -> Option<T>;
+}
+
 pub trait List<T> {
     fn size(&self) -> usize;
     fn get(&self, index: usize) -> Option<&T>;
     fn set(&mut self, index: usize, item: T) -> Result<(), CollectionError>;
     fn add(&mut self, index: usize, item: T) -> Result<(), CollectionError>;
     fn remove(&mut self, index: usize) -> Option<T>;
+
+    /// O(n) by default, implementors with random access override it with an O(1) swap.
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), CollectionError> {
+        if a >= self.size() || b >= self.size() {
+            return Err(OUT_OF_BOUND_ERROR);
+        }
+        if a == b {
+            return Ok(());
+        }
+
+        let (a, b) = if a < b { (a, b) } else { (b, a) };
+        let (x, y) = match (self.remove(b), self.remove(a)) {
+            (Some(y), Some(x)) => (x, y),
+            _ => unreachable!(),
+        };
+        self.add(a, y)?;
+        self.add(b, x)
+    }
 }
 
-pub trait USet<T> {
+pub trait USet<T: Hash + Eq> {
     fn size(&self) -> usize;
     fn add(&mut self, item: T) -> bool;
-    fn remove(&mut self, item: T) -> Option<T>;
-    fn find(&self, item: &T) -> Option<&T>;
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized;
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized;
+
+    fn remove<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.take(item).is_some()
+    }
+
+    /// Adds `item`, returning the equal item it replaces.
+    fn replace(&mut self, item: T) -> Option<T> {
+        let replaced = self.take(&item);
+        self.add(item);
+        replaced
+    }
+
+    /// Item equal to `item`, created by `f` when absent. `f` must return an item equal to `item`.
+    fn get_or_insert_with<Q, F>(&mut self, item: &Q, f: F) -> &T
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&Q) -> T,
+    {
+        if self.find(item).is_none() {
+            self.add(f(item));
+        }
+        self.find(item).expect("created item equals the given one")
+    }
 }
 
-pub trait SSet<T> {
+/// A sorted set. The complexities depend on the implementor and are documented on each of them.
+pub trait SSet<T: Ord> {
+    /// Number of items.
     fn size(&self) -> usize;
+    /// Adds `item` unless an equal item is present.
     fn add(&mut self, item: T) -> bool;
-    fn remove(&mut self, item: T) -> Option<T>;
-    fn find(&self, item: &T) -> Option<&T>;
+    /// Removes and returns the item equal to `item`.
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized;
+    /// Smallest item greater than or equal to `item`.
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized;
+    /// Smallest item strictly greater than `item`.
+    fn successor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized;
+    /// Largest item strictly less than `item`.
+    fn predecessor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized;
+    /// Smallest item.
+    fn first(&self) -> Option<&T>;
+    /// Largest item.
+    fn last(&self) -> Option<&T>;
+    /// Removes the smallest item.
+    fn pop_first(&mut self) -> Option<T>;
+    /// Removes the largest item.
+    fn pop_last(&mut self) -> Option<T>;
+
+    /// Removes the item equal to `item`.
+    fn remove<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.take(item).is_some()
+    }
+
+    /// Item equal to `item`.
+    fn get<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(item).filter(|found| (*found).borrow() == item)
+    }
+
+    /// Adds `item`, returning the equal item it replaces.
+    fn replace(&mut self, item: T) -> Option<T> {
+        let replaced = self.take(&item);
+        self.add(item);
+        replaced
+    }
+
+    /// Item equal to `item`, created by `f` when absent. `f` must return an item equal to `item`.
+    fn get_or_insert_with<Q, F>(&mut self, item: &Q, f: F) -> &T
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        F: FnOnce(&Q) -> T,
+    {
+        if self.get(item).is_none() {
+            self.add(f(item));
+        }
+        self.get(item).expect("created item equals the given one")
+    }
+
+    /// Items within `bounds` in ascending order.
+    ///
+    /// The default steps through `successor` and `predecessor`, a query per item, the search
+    /// trees walk their nodes instead.
+    fn range<Q, R>(&self, bounds: R) -> Range<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let first = match bounds.start_bound() {
+            Bound::Included(item) => self.find(item),
+            Bound::Excluded(item) => self.successor(item),
+            Bound::Unbounded => self.first(),
+        };
+        let last = match bounds.end_bound() {
+            Bound::Included(item) => self.get(item).or_else(|| self.predecessor(item)),
+            Bound::Excluded(item) => self.predecessor(item),
+            Bound::Unbounded => self.last(),
+        };
+
+        Range::new(
+            std::iter::successors(first, move |item| self.successor::<T>(*item)),
+            std::iter::successors(last, move |item| self.predecessor::<T>(*item)),
+        )
+    }
+
+    /// Number of items within `bounds`.
+    fn count_in_range<Q, R>(&self, bounds: R) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.range(bounds).count()
+    }
 }
 
-#[derive(Debug)]
+/// The items of a sorted set between two bounds, walked up from the lower one and down from the
+/// upper one until both ends meet.
+pub struct Range<'a, T> {
+    ascending: Box<dyn Iterator<Item = &'a T> + 'a>,
+    descending: Box<dyn Iterator<Item = &'a T> + 'a>,
+    front: Option<&'a T>,
+    back: Option<&'a T>,
+}
+
+impl<'a, T: Ord> Range<'a, T> {
+    /// `ascending` starts at the smallest item within the bounds, `descending` at the largest.
+    pub(in crate) fn new<A, D>(mut ascending: A, mut descending: D) -> Range<'a, T>
+    where
+        A: Iterator<Item = &'a T> + 'a,
+        D: Iterator<Item = &'a T> + 'a,
+    {
+        let front = ascending.next();
+        let back = descending.next();
+        let (front, back) = match (front, back) {
+            (Some(first), Some(last)) if first <= last => (front, back),
+            _ => (None, None),
+        };
+
+        Range {
+            ascending: Box::new(ascending),
+            descending: Box::new(descending),
+            front,
+            back,
+        }
+    }
+
+    fn is_last(&self) -> bool {
+        self.front == self.back
+    }
+
+    fn finish(&mut self) {
+        self.front = None;
+        self.back = None;
+    }
+}
+
+impl<'a, T: Ord> Iterator for Range<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let item = self.front?;
+        if self.is_last() {
+            self.finish();
+        } else {
+            self.front = self.ascending.next();
+        }
+        Some(item)
+    }
+}
+
+impl<'a, T: Ord> DoubleEndedIterator for Range<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        let item = self.back?;
+        if self.is_last() {
+            self.finish();
+        } else {
+            self.back = self.descending.next();
+        }
+        Some(item)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Range<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Range")
+            .field("front", &self.front)
+            .field("back", &self.back)
+            .finish()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct CollectionError {
     message: &'static str,
 }
@@ -67,3 +343,304 @@ impl Error for CollectionError {}
 pub const OUT_OF_BOUND_ERROR: CollectionError = CollectionError {
     message: "out of bound",
 };
+
+pub const INVALID_HANDLE_ERROR: CollectionError = CollectionError {
+    message: "invalid handle",
+};
+
+pub const INVALID_KEY_ERROR: CollectionError = CollectionError {
+    message: "invalid key",
+};
+
+pub const INVALID_ARGUMENT_ERROR: CollectionError = CollectionError {
+    message: "invalid argument",
+};
+
+pub const NEGATIVE_CYCLE_ERROR: CollectionError = CollectionError {
+    message: "negative cycle",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::{AddressablePriorityQueue, PriorityQueue, SSet};
+    use super::{INVALID_HANDLE_ERROR, INVALID_KEY_ERROR};
+    use std::ops::Bound;
+
+    // the `SSet` contract on the items 1 to 5, shared by its implementors
+    pub(in crate) fn sset<S: SSet<u32>>(mut set: S) {
+        assert_eq!(set.size(), 0);
+        assert_eq!(set.find(&0), None);
+        assert_eq!(set.first(), None);
+        assert_eq!(set.pop_last(), None);
+
+        assert!(set.add(3));
+        assert!(set.add(1));
+        assert!(set.add(5));
+        assert!(set.add(2));
+        assert!(set.add(4));
+        assert!(!set.add(3));
+
+        assert_eq!(set.size(), 5);
+
+        assert_eq!(set.find(&0), Some(&1));
+        assert_eq!(set.find(&3), Some(&3));
+        assert_eq!(set.find(&5), Some(&5));
+        assert_eq!(set.find(&6), None);
+
+        assert_eq!(set.successor(&3), Some(&4));
+        assert_eq!(set.successor(&5), None);
+        assert_eq!(set.predecessor(&3), Some(&2));
+        assert_eq!(set.predecessor(&1), None);
+        assert_eq!(set.predecessor(&100), Some(&5));
+        assert_eq!(set.first(), Some(&1));
+        assert_eq!(set.last(), Some(&5));
+
+        assert_eq!(set.take(&3), Some(3));
+        assert_eq!(set.take(&3), None);
+
+        assert_eq!(set.size(), 4);
+
+        assert_eq!(set.find(&3), Some(&4));
+
+        assert!(set.remove(&1));
+        assert!(!set.remove(&1));
+        assert_eq!(set.pop_first(), Some(2));
+        assert_eq!(set.pop_last(), Some(5));
+        assert_eq!(set.take(&4), Some(4));
+
+        assert_eq!(set.size(), 0);
+        assert_eq!(set.find(&0), None);
+        assert_eq!(set.predecessor(&0), None);
+        assert_eq!(set.first(), None);
+    }
+
+    // the range iteration of an `SSet` of the tens up to 90
+    pub(in crate) fn range<S: SSet<u32>>(mut set: S) {
+        assert_eq!(set.range(..).next(), None);
+        for item in 0..10 {
+            set.add(item * 10);
+        }
+
+        let items: Vec<&u32> = set.range(20..50).collect();
+        assert_eq!(items, vec![&20, &30, &40]);
+
+        let items: Vec<&u32> = set.range(15..=50).rev().collect();
+        assert_eq!(items, vec![&50, &40, &30, &20]);
+
+        let mut items = set.range(..25);
+        assert_eq!(items.next(), Some(&0));
+        assert_eq!(items.next_back(), Some(&20));
+        assert_eq!(items.next_back(), Some(&10));
+        assert_eq!(items.next(), None);
+        assert_eq!(items.next_back(), None);
+
+        assert_eq!(set.range(85..).count(), 1);
+        assert_eq!(set.range(41..49).next(), None);
+        assert_eq!(
+            set.range((Bound::Excluded(40), Bound::Excluded(50))).next(),
+            None
+        );
+
+        assert_eq!(set.count_in_range(..), 10);
+        assert_eq!(set.count_in_range(10..=90), 9);
+        assert_eq!(set.count_in_range(11..19), 0);
+    }
+
+    // the lookups by `str` and the entry-style methods of an `SSet` of strings
+    pub(in crate) fn entry<S: SSet<String>>(mut set: S) {
+        assert_eq!(set.get_or_insert_with("b", |item| item.to_string()), "b");
+        assert_eq!(set.get_or_insert_with("b", |_| unreachable!()), "b");
+        assert!(set.add(String::from("a")));
+        assert_eq!(set.replace(String::from("c")), None);
+        assert_eq!(set.replace(String::from("c")), Some(String::from("c")));
+
+        assert_eq!(set.size(), 3);
+
+        assert_eq!(set.get("a"), Some(&String::from("a")));
+        assert_eq!(set.get("bb"), None);
+        assert_eq!(set.find("bb"), Some(&String::from("c")));
+        assert_eq!(set.successor("a"), Some(&String::from("b")));
+        assert_eq!(set.predecessor("a"), None);
+        let bounds = (Bound::Included("a"), Bound::Excluded("c"));
+        assert_eq!(set.count_in_range::<str, _>(bounds), 2);
+
+        assert_eq!(set.take("b"), Some(String::from("b")));
+        assert!(set.remove("a"));
+        assert!(!set.remove("a"));
+
+        assert_eq!(set.size(), 1);
+    }
+
+    // the `PriorityQueue` contract, shared by its implementors
+    pub(in crate) fn priority_queue<P: PriorityQueue<u32>>(mut heap: P) {
+        assert_eq!(heap.size(), 0);
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.remove_min(), None);
+
+        heap.add(3);
+        heap.add(1);
+        heap.add(4);
+        heap.add(1);
+        heap.add(5);
+
+        assert_eq!(heap.size(), 5);
+        assert_eq!(heap.peek(), Some(&1));
+
+        assert_eq!(heap.remove_min(), Some(1));
+        assert_eq!(heap.remove_min(), Some(1));
+        assert_eq!(heap.remove_min(), Some(3));
+
+        heap.add(2);
+
+        assert_eq!(heap.remove_min(), Some(2));
+        assert_eq!(heap.remove_min(), Some(4));
+        assert_eq!(heap.remove_min(), Some(5));
+        assert_eq!(heap.remove_min(), None);
+
+        assert_eq!(heap.size(), 0);
+
+        // descending, ascending and repeated items
+        for item in (0..100)
+            .rev()
+            .chain(0..100)
+            .chain(std::iter::repeat_n(50, 10))
+        {
+            heap.add(item);
+        }
+        let mut items = vec![];
+        while let Some(item) = heap.remove_min() {
+            items.push(item);
+        }
+        let mut expected: Vec<u32> = (0..100)
+            .chain(0..100)
+            .chain(std::iter::repeat_n(50, 10))
+            .collect();
+        expected.sort();
+        assert_eq!(items, expected);
+    }
+
+    // the `AddressablePriorityQueue` contract, shared by its implementors
+    pub(in crate) fn addressable<P: AddressablePriorityQueue<u32>>(mut heap: P) {
+        let a = heap.insert(50);
+        let b = heap.insert(40);
+        let c = heap.insert(30);
+        let d = heap.insert(20);
+        let e = heap.insert(10);
+
+        assert_eq!(heap.decrease_key(&a, 5), Ok(()));
+        assert_eq!(heap.peek(), Some(&5));
+        assert_eq!(heap.decrease_key(&a, 6), Err(INVALID_KEY_ERROR));
+
+        assert_eq!(heap.increase_key(&e, 45), Ok(()));
+        assert_eq!(heap.increase_key(&e, 44), Err(INVALID_KEY_ERROR));
+
+        assert!(heap.contains(&c));
+        assert_eq!(heap.remove(&c), Some(30));
+        assert!(!heap.contains(&c));
+        assert_eq!(heap.remove(&c), None);
+        assert_eq!(heap.decrease_key(&c, 0), Err(INVALID_HANDLE_ERROR));
+        assert_eq!(heap.increase_key(&c, 60), Err(INVALID_HANDLE_ERROR));
+
+        assert_eq!(heap.remove_min(), Some(5));
+        assert!(!heap.contains(&a));
+        assert_eq!(heap.remove_min(), Some(20));
+        assert_eq!(heap.remove(&b), Some(40));
+        assert_eq!(heap.remove(&e), Some(45));
+        assert_eq!(heap.remove(&d), None);
+
+        assert_eq!(heap.size(), 0);
+
+        // every item moved by a key change, the rest left in order
+        let handles: Vec<_> = (0..100).map(|item| heap.insert(item * 10 + 5)).collect();
+        for (item, handle) in handles.iter().enumerate() {
+            let item = item as u32;
+            match item % 3 {
+                0 => assert_eq!(heap.decrease_key(handle, item * 10), Ok(())),
+                1 => assert_eq!(heap.increase_key(handle, item * 10 + 9), Ok(())),
+                _ => assert_eq!(heap.remove(handle), Some(item * 10 + 5)),
+            }
+        }
+        let mut items = vec![];
+        while let Some(item) = heap.remove_min() {
+            items.push(item);
+        }
+        let expected: Vec<u32> = (0..100)
+            .filter(|item| item % 3 != 2)
+            .map(|item| {
+                if item % 3 == 0 {
+                    item * 10
+                } else {
+                    item * 10 + 9
+                }
+            })
+            .collect();
+        assert_eq!(items, expected);
+    }
+
+    // handles of `other` are rejected by `heap`, both holding the same items
+    pub(in crate) fn foreign_handle<P: AddressablePriorityQueue<u32>>(mut heap: P, mut other: P) {
+        let handles: Vec<_> = (0..10).map(|item| other.insert(item)).collect();
+        for item in 0..10 {
+            heap.insert(item);
+        }
+        // links the remaining items below one another
+        assert_eq!(other.remove_min(), Some(0));
+
+        for handle in handles.iter() {
+            assert!(!heap.contains(handle));
+            assert_eq!(heap.remove(handle), None);
+            assert_eq!(heap.decrease_key(handle, 0), Err(INVALID_HANDLE_ERROR));
+            assert_eq!(heap.increase_key(handle, 20), Err(INVALID_HANDLE_ERROR));
+        }
+        assert_eq!(heap.size(), 10);
+        assert_eq!(other.size(), 9);
+
+        for item in 0..10 {
+            assert_eq!(heap.remove_min(), Some(item));
+        }
+        assert_eq!(other.remove(&handles[5]), Some(5));
+        for item in (1..10).filter(|item| *item != 5) {
+            assert_eq!(other.remove_min(), Some(item));
+        }
+    }
+
+    // handles stay valid in the heap `other` is melded into
+    pub(in crate) fn meld<P>(mut heap: P, mut other: P, meld: fn(&mut P, P))
+    where
+        P: AddressablePriorityQueue<u32> + Default,
+    {
+        meld(&mut heap, P::default());
+        assert_eq!(heap.size(), 0);
+
+        let mut handles = vec![];
+        for item in 1..=1000 {
+            if item % 2 == 0 {
+                handles.push(heap.insert(item));
+            } else {
+                handles.push(other.insert(item));
+            }
+        }
+        // links the items below one another before the meld
+        assert_eq!(other.remove_min(), Some(1));
+        other.insert(1);
+
+        meld(&mut heap, other);
+        assert_eq!(heap.size(), 1000);
+
+        assert_eq!(heap.decrease_key(&handles[999], 0), Ok(()));
+        assert_eq!(heap.remove_min(), Some(0));
+        assert_eq!(heap.remove(&handles[499]), Some(500));
+        assert_eq!(heap.increase_key(&handles[2], 2000), Ok(()));
+
+        for item in (1..1000).filter(|item| *item != 3 && *item != 500) {
+            assert_eq!(heap.remove_min(), Some(item));
+        }
+        assert_eq!(heap.remove_min(), Some(2000));
+        assert_eq!(heap.size(), 0);
+
+        let mut empty = P::default();
+        let handle = heap.insert(7);
+        meld(&mut empty, heap);
+        assert_eq!(empty.remove(&handle), Some(7));
+    }
+}
@@ -1,14 +1,64 @@
 use open_data_structures::array_deque::ArrayDeque;
 use open_data_structures::array_queue::ArrayQueue;
 use open_data_structures::array_stack::ArrayStack;
+use open_data_structures::avl_tree::AvlTree;
+use open_data_structures::b_tree::BTree;
+use open_data_structures::binary_heap::BinaryHeap;
+use open_data_structures::binary_trie::BinaryTrie;
+use open_data_structures::chained_hash_table::ChainedHashTable;
+use open_data_structures::fibonacci_heap::FibonacciHeap;
+use open_data_structures::graph::{
+    self, AdjacencyLists, AdjacencyMatrix, Graph, WeightedAdjacencyLists, WeightedGraph,
+};
+use open_data_structures::indexed_binary_heap::IndexedBinaryHeap;
+use open_data_structures::meldable_heap::MeldableHeap;
+use open_data_structures::min_max_heap::MinMaxHeap;
+use open_data_structures::pairing_heap::PairingHeap;
+use open_data_structures::red_black_tree::RedBlackTree;
+use open_data_structures::selection;
+use open_data_structures::sorting;
+use open_data_structures::union_find::UnionFind;
+use open_data_structures::van_emde_boas_tree::VanEmdeBoasTree;
+use open_data_structures::x_fast_trie::XFastTrie;
+use open_data_structures::y_fast_trie::YFastTrie;
+use open_data_structures::AddressablePriorityQueue;
 use open_data_structures::Deque;
+use open_data_structures::PriorityQueue;
 use open_data_structures::Queue;
+use open_data_structures::SSet;
 use open_data_structures::Stack;
+use open_data_structures::USet;
 
 fn main() {
     array_stack();
     array_queue();
     array_deque();
+    red_black_tree();
+    avl_tree();
+    binary_heap();
+    meldable_heap();
+    indexed_binary_heap();
+    pairing_heap();
+    fibonacci_heap();
+    min_max_heap();
+    sorting();
+    selection();
+    adjacency_matrix();
+    adjacency_lists();
+    traversal();
+    shortest_path();
+    graph_ordering();
+    spanning_tree();
+    max_flow();
+    matching();
+    graph_formats();
+    union_find();
+    binary_trie();
+    chained_hash_table();
+    x_fast_trie();
+    y_fast_trie();
+    van_emde_boas_tree();
+    b_tree();
 }
 
 fn array_stack() {
@@ -54,3 +104,440 @@ fn array_deque() {
     println!("ArrayDeque.remove_last:  {:?}", deque.remove_last());
     println!("{:?}", deque);
 }
+
+fn red_black_tree() {
+    let mut tree = RedBlackTree::new();
+
+    tree.add(3);
+    tree.add(1);
+    tree.add(42);
+    tree.add(5);
+
+    println!("{:?}", tree);
+    println!("RedBlackTree.find(4):   {:?}", tree.find(&4));
+    println!("RedBlackTree.take(5):   {:?}", tree.take(&5));
+    println!("RedBlackTree.find(4):   {:?}", tree.find(&4));
+    println!("{:?}", tree);
+}
+
+fn avl_tree() {
+    let mut tree = AvlTree::new();
+
+    tree.add(3);
+    tree.add(1);
+    tree.add(42);
+    tree.add(5);
+
+    println!("{:?}", tree);
+    println!("AvlTree.find(4): {:?}", tree.find(&4));
+    println!("AvlTree.take(5): {:?}", tree.take(&5));
+    println!("AvlTree.find(4): {:?}", tree.find(&4));
+    println!("AvlTree.is_valid(): {}", tree.is_valid());
+    println!("{:?}", tree);
+}
+
+fn binary_heap() {
+    let mut heap = BinaryHeap::new();
+
+    heap.add(3);
+    heap.add(1);
+    heap.add(42);
+    heap.add(5);
+
+    println!("{:?}", heap);
+    println!("BinaryHeap.remove_min: {:?}", heap.remove_min());
+    println!("BinaryHeap.remove_min: {:?}", heap.remove_min());
+    println!("{:?}", heap);
+}
+
+fn meldable_heap() {
+    let mut heap = MeldableHeap::new();
+    let mut other = MeldableHeap::new();
+
+    heap.add(3);
+    heap.add(42);
+    other.add(1);
+    let handle = other.insert(5);
+
+    heap.meld(other);
+
+    println!("MeldableHeap.remove(handle): {:?}", heap.remove(&handle));
+    println!("MeldableHeap.remove_min:     {:?}", heap.remove_min());
+    println!("MeldableHeap.remove_min:     {:?}", heap.remove_min());
+    println!("MeldableHeap.size:           {:?}", heap.size());
+}
+
+fn indexed_binary_heap() {
+    let mut heap = IndexedBinaryHeap::new();
+
+    heap.add(3);
+    heap.add(1);
+    let handle = heap.insert(42);
+    heap.add(5);
+
+    println!("{:?}", heap);
+    println!(
+        "IndexedBinaryHeap.decrease_key: {:?}",
+        heap.decrease_key(&handle, 0)
+    );
+    println!("IndexedBinaryHeap.remove_min:   {:?}", heap.remove_min());
+    println!("IndexedBinaryHeap.remove_min:   {:?}", heap.remove_min());
+    println!("{:?}", heap);
+}
+
+fn pairing_heap() {
+    let mut heap = PairingHeap::new();
+    let mut other = PairingHeap::new();
+
+    heap.add(3);
+    heap.add(42);
+    other.add(1);
+    let handle = other.insert(5);
+
+    heap.meld(other);
+
+    println!(
+        "PairingHeap.decrease_key: {:?}",
+        heap.decrease_key(&handle, 0)
+    );
+    println!("PairingHeap.remove_min:   {:?}", heap.remove_min());
+    println!("PairingHeap.remove_min:   {:?}", heap.remove_min());
+    println!("PairingHeap.size:         {:?}", heap.size());
+}
+
+fn fibonacci_heap() {
+    let mut heap = FibonacciHeap::new();
+    let mut other = FibonacciHeap::new();
+
+    heap.add(3);
+    heap.add(42);
+    other.add(1);
+    let handle = other.insert(5);
+
+    heap.meld(other);
+
+    println!(
+        "FibonacciHeap.decrease_key: {:?}",
+        heap.decrease_key(&handle, 0)
+    );
+    println!("FibonacciHeap.remove_min:   {:?}", heap.remove_min());
+    println!("FibonacciHeap.remove_min:   {:?}", heap.remove_min());
+    println!("FibonacciHeap.size:         {:?}", heap.size());
+}
+
+fn min_max_heap() {
+    let mut heap = MinMaxHeap::new();
+
+    heap.add(3);
+    heap.add(1);
+    heap.add(42);
+    heap.add(5);
+
+    println!("{:?}", heap);
+    println!("MinMaxHeap.pop_min: {:?}", heap.pop_min());
+    println!("MinMaxHeap.pop_max: {:?}", heap.pop_max());
+    println!("{:?}", heap);
+}
+
+fn sorting() {
+    let mut stack = ArrayStack::new();
+
+    stack.push(3);
+    stack.push(1);
+    stack.push(42);
+    stack.push(5);
+
+    sorting::merge_sort(&mut stack);
+    println!("sorting::merge_sort: {:?}", stack);
+
+    let mut items = vec![3, 1, 42, 5];
+    sorting::quick_sort_slice_by(&mut items, |a, b| b.cmp(a));
+    println!("sorting::quick_sort_slice_by: {:?}", items);
+
+    let mut timestamps = vec![1_700_000_042u64, 1_600_000_000, 1_700_000_001];
+    println!(
+        "sorting::radix_sort_slice: {:?}",
+        sorting::radix_sort_slice(&mut timestamps, 8, |timestamp| *timestamp)
+    );
+    println!("{:?}", timestamps);
+}
+
+fn selection() {
+    let mut deque = ArrayDeque::new();
+
+    deque.add_last(3);
+    deque.add_last(1);
+    deque.add_last(42);
+    deque.add_last(5);
+    deque.add_last(8);
+
+    println!("selection::median:     {:?}", selection::median(&mut deque));
+    println!(
+        "selection::select_nth: {:?}",
+        selection::select_nth(&mut deque, 3)
+    );
+    println!(
+        "selection::top_k:      {:?}",
+        selection::top_k(&mut deque, 2)
+    );
+    println!("{:?}", deque);
+}
+
+fn adjacency_matrix() {
+    let mut graph = AdjacencyMatrix::new(3);
+
+    println!("AdjacencyMatrix.add_edge:  {:?}", graph.add_edge(0, 1));
+    println!("AdjacencyMatrix.add_edge:  {:?}", graph.add_edge(2, 1));
+    println!("AdjacencyMatrix.add_edge:  {:?}", graph.add_edge(3, 1));
+    println!("AdjacencyMatrix.has_edge:  {:?}", graph.has_edge(0, 1));
+    println!(
+        "AdjacencyMatrix.in_edges:  {:?}",
+        graph.in_edges(1).iter().collect::<Vec<_>>()
+    );
+}
+
+fn adjacency_lists() {
+    let mut graph = AdjacencyLists::new(3);
+
+    println!("AdjacencyLists.add_edge:  {:?}", graph.add_edge(0, 1));
+    println!("AdjacencyLists.add_edge:  {:?}", graph.add_edge(2, 1));
+    println!("AdjacencyLists.add_edge:  {:?}", graph.add_edge(3, 1));
+    println!("AdjacencyLists.has_edge:  {:?}", graph.has_edge(0, 1));
+    println!(
+        "AdjacencyLists.in_edges:  {:?}",
+        graph.in_edges(1).iter().collect::<Vec<_>>()
+    );
+}
+
+fn traversal() {
+    let mut graph = AdjacencyLists::new(4);
+    graph.add_edge(0, 1).unwrap();
+    graph.add_edge(0, 2).unwrap();
+    graph.add_edge(1, 3).unwrap();
+
+    for visit in graph::bfs(&graph, 0) {
+        println!("graph::bfs: {:?}", visit);
+    }
+    for visit in graph::dfs_with(1, |x: &u32| {
+        vec![x * 2, x * 3].into_iter().filter(|y| *y < 10)
+    }) {
+        println!("graph::dfs_with: {:?}", visit);
+    }
+}
+
+fn shortest_path() {
+    let mut graph = WeightedAdjacencyLists::new(4);
+    graph.add_edge(0, 1, 4).unwrap();
+    graph.add_edge(0, 2, 1).unwrap();
+    graph.add_edge(2, 1, 2).unwrap();
+    graph.add_edge(1, 3, 1).unwrap();
+
+    let paths = graph::dijkstra(&graph, 0).unwrap();
+    println!("graph::dijkstra: distance {:?}", paths.distance(3));
+    println!("graph::dijkstra: path {:?}", paths.path(3));
+    println!(
+        "graph::bellman_ford: distance {:?}",
+        graph::bellman_ford(&graph, 0).unwrap().distance(3)
+    );
+    println!(
+        "graph::a_star: {:?}",
+        graph::a_star(&graph, 0, 3, |_| 0).unwrap()
+    );
+}
+
+fn graph_ordering() {
+    let mut graph = AdjacencyLists::new(5);
+    graph.add_edge(0, 1).unwrap();
+    graph.add_edge(1, 2).unwrap();
+    graph.add_edge(2, 0).unwrap();
+    graph.add_edge(2, 3).unwrap();
+    graph.add_edge(4, 3).unwrap();
+
+    println!(
+        "graph::topological_sort: {:?}",
+        graph::topological_sort(&graph)
+    );
+    println!("graph::find_cycle: {:?}", graph::find_cycle(&graph));
+    println!("graph::tarjan_scc: {:?}", graph::tarjan_scc(&graph));
+    println!("graph::kosaraju_scc: {:?}", graph::kosaraju_scc(&graph));
+
+    graph.remove_edge(2, 0);
+    println!(
+        "graph::topological_sort: {:?}",
+        graph::topological_sort(&graph)
+    );
+    println!(
+        "graph::topological_sort_dfs: {:?}",
+        graph::topological_sort_dfs(&graph)
+    );
+}
+
+fn spanning_tree() {
+    let mut graph = WeightedAdjacencyLists::new(4);
+    graph.add_edge(0, 1, 3).unwrap();
+    graph.add_edge(1, 2, 1).unwrap();
+    graph.add_edge(0, 2, 2).unwrap();
+    graph.add_edge(2, 3, 5).unwrap();
+
+    println!("graph::kruskal: {:?}", graph::kruskal(&graph));
+    println!("graph::prim: {:?}", graph::prim(&graph));
+}
+
+fn max_flow() {
+    let mut graph = WeightedAdjacencyLists::new(4);
+    graph.add_edge(0, 1, 3).unwrap();
+    graph.add_edge(0, 2, 2).unwrap();
+    graph.add_edge(1, 2, 1).unwrap();
+    graph.add_edge(1, 3, 2).unwrap();
+    graph.add_edge(2, 3, 3).unwrap();
+
+    let flow = graph::dinic(&graph, 0, 3).unwrap();
+    println!("graph::dinic: value {}", flow.value());
+    println!("graph::dinic: min_cut {:?}", flow.min_cut());
+    println!(
+        "graph::edmonds_karp: value {}",
+        graph::edmonds_karp(&graph, 0, 3).unwrap().value()
+    );
+}
+
+fn matching() {
+    let mut graph = AdjacencyLists::new(6);
+    graph.add_edge(0, 3).unwrap();
+    graph.add_edge(0, 4).unwrap();
+    graph.add_edge(1, 3).unwrap();
+    graph.add_edge(2, 4).unwrap();
+    graph.add_edge(2, 5).unwrap();
+
+    let matching = graph::hopcroft_karp(&graph, 3).unwrap();
+    println!("graph::hopcroft_karp: {:?}", matching.edges());
+}
+
+fn graph_formats() {
+    let graph = graph::read_edge_list("0 1\n1 2\n3\n", 1000).unwrap();
+    print!(
+        "graph::write_dimacs:\n{}",
+        graph::write_dimacs(&graph, "edge")
+    );
+    print!("graph::write_dot:\n{}", graph::write_dot(&graph));
+
+    match graph::read_weighted_dimacs::<u32>("p sp 2 1\na 1 3 5\n", 1000) {
+        Ok(_) => (),
+        Err(error) => println!("graph::read_weighted_dimacs: {}", error),
+    }
+}
+
+fn union_find() {
+    let mut union_find = UnionFind::with_size(5);
+    union_find.union(0, 3).unwrap();
+    union_find.union(4, 3).unwrap();
+    let x = union_find.make_set();
+    union_find.union(x, 1).unwrap();
+
+    println!("union_find.same_set(0, 4): {}", union_find.same_set(0, 4));
+    println!("union_find.set_size(0): {:?}", union_find.set_size(0));
+    for set in union_find.sets() {
+        println!("union_find.sets: {:?}", set);
+    }
+}
+
+fn binary_trie() {
+    let mut trie = BinaryTrie::new();
+
+    trie.add(3u32);
+    trie.add(1);
+    trie.add(42);
+    trie.add(5);
+
+    println!("BinaryTrie.find(4): {:?}", trie.find(&4));
+    println!("BinaryTrie.predecessor(4): {:?}", trie.predecessor(&4));
+    println!("BinaryTrie.take(5): {:?}", trie.take(&5));
+    println!("BinaryTrie.find(4): {:?}", trie.find(&4));
+}
+
+fn chained_hash_table() {
+    let mut table = ChainedHashTable::new();
+
+    table.add("one");
+    table.add("two");
+    table.add("three");
+
+    println!("ChainedHashTable.find(two): {:?}", table.find("two"));
+    println!("ChainedHashTable.take(two): {:?}", table.take("two"));
+    println!("ChainedHashTable.find(two): {:?}", table.find("two"));
+    println!("ChainedHashTable.size(): {}", table.size());
+}
+
+fn x_fast_trie() {
+    let mut trie = XFastTrie::new();
+
+    trie.add(3u64);
+    trie.add(1);
+    trie.add(1 << 40);
+    trie.add(5);
+
+    println!("XFastTrie.find_key(4): {:?}", trie.find_key(4));
+    println!("XFastTrie.successor_key(5): {:?}", trie.successor_key(5));
+    println!("XFastTrie.predecessor(&4): {:?}", trie.predecessor(&4));
+    println!("XFastTrie.take(5): {:?}", trie.take(&5));
+    println!("XFastTrie.find_key(4): {:?}", trie.find_key(4));
+}
+
+fn y_fast_trie() {
+    let mut trie = YFastTrie::new();
+
+    for item in 0..1000u64 {
+        trie.add(item * item);
+    }
+
+    println!("YFastTrie.find_key(1000): {:?}", trie.find_key(1000));
+    println!(
+        "YFastTrie.successor_key(1024): {:?}",
+        trie.successor_key(1024)
+    );
+    println!(
+        "YFastTrie.predecessor(&1000): {:?}",
+        trie.predecessor(&1000)
+    );
+    println!("YFastTrie.take(1024): {:?}", trie.take(&1024));
+    println!("YFastTrie.find_key(1000): {:?}", trie.find_key(1000));
+}
+
+fn van_emde_boas_tree() {
+    let mut tree = VanEmdeBoasTree::with_bits(24).unwrap();
+
+    for item in (0..1 << 24).step_by(1000) {
+        tree.add(item);
+    }
+
+    println!("VanEmdeBoasTree.universe(): {}", tree.universe());
+    println!("VanEmdeBoasTree.contains(5000): {}", tree.contains(5000));
+    println!(
+        "VanEmdeBoasTree.successor_key(5000): {:?}",
+        tree.successor_key(5000)
+    );
+    println!(
+        "VanEmdeBoasTree.predecessor(&5000): {:?}",
+        tree.predecessor(&5000)
+    );
+    println!(
+        "VanEmdeBoasTree.remove_key(5000): {}",
+        tree.remove_key(5000)
+    );
+    println!("VanEmdeBoasTree.find(4001): {:?}", tree.find(&4001));
+}
+
+fn b_tree() {
+    let mut tree = BTree::with_block_size(4).unwrap();
+
+    for item in (0..100).rev() {
+        tree.add(item * 3);
+    }
+
+    println!("BTree.is_valid(): {}", tree.is_valid());
+    println!("BTree.find(10): {:?}", tree.find(&10));
+    println!("BTree.successor(12): {:?}", tree.successor(&12));
+    println!("BTree.predecessor(12): {:?}", tree.predecessor(&12));
+    println!("BTree.take(12): {:?}", tree.take(&12));
+    println!("BTree.find(10): {:?}", tree.find(&10));
+    println!("BTree.size(): {}", tree.size());
+}
@@ -0,0 +1,330 @@
+use crate::owner::Owner;
+use crate::random::Random;
+use crate::PriorityQueue;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    item: T,
+    owner: Owner,
+    links: RefCell<Links<T>>,
+}
+
+#[derive(Debug)]
+struct Links<T> {
+    parent: Weak<Node<T>>,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> Node<T> {
+    fn new(item: T, owner: Owner) -> Rc<Node<T>> {
+        Rc::new(Node {
+            item,
+            owner,
+            links: RefCell::new(Links {
+                parent: Weak::new(),
+                left: None,
+                right: None,
+            }),
+        })
+    }
+
+    fn set_parent(&self, parent: Weak<Node<T>>) {
+        self.links.borrow_mut().parent = parent;
+    }
+}
+
+/// Refers to an item of a heap, stays valid when the heap is melded into another one.
+pub struct Handle<T>(Weak<Node<T>>);
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle")
+    }
+}
+
+/// Subtrees are merged along random paths, so `insert`, `remove` and `meld` take O(log n)
+/// expected time. Handles of another heap are rejected, those of a heap melded into this one stay
+/// valid.
+///
+/// The nodes and their handles are reference counted, so a heap is neither `Send` nor `Sync`:
+/// heaps are melded within the thread that built them.
+#[derive(Debug)]
+pub struct MeldableHeap<T> {
+    root: Link<T>,
+    size: usize,
+    owner: Owner,
+    random: Random,
+}
+
+impl<T: Ord> MeldableHeap<T> {
+    pub fn new() -> MeldableHeap<T> {
+        MeldableHeap {
+            root: None,
+            size: 0,
+            owner: Owner::new(),
+            random: Random::new(),
+        }
+    }
+
+    pub fn with_seed(seed: u64) -> MeldableHeap<T> {
+        MeldableHeap {
+            root: None,
+            size: 0,
+            owner: Owner::new(),
+            random: Random::with_seed(seed),
+        }
+    }
+
+    /// Adds `x` and returns its handle. O(log n) expected.
+    pub fn insert(&mut self, x: T) -> Handle<T> {
+        let node = Node::new(x, self.owner.clone());
+        let handle = Handle(Rc::downgrade(&node));
+
+        let root = self.root.take();
+        self.root = self.merge(root, Some(node));
+        self.size += 1;
+
+        handle
+    }
+
+    /// Moves all the items of `other` into this heap. O(log n) expected.
+    pub fn meld(&mut self, mut other: MeldableHeap<T>) {
+        let root = self.root.take();
+        self.root = self.merge(root, other.root.take());
+        self.size += other.size;
+        other.owner.forward_to(&self.owner);
+    }
+
+    /// Removes the item of `handle`, `None` unless it comes from this heap or one melded into it.
+    /// O(log n) expected.
+    pub fn remove(&mut self, handle: &Handle<T>) -> Option<T> {
+        let node = handle.0.upgrade()?;
+        if !node.owner.is(&self.owner) {
+            return None;
+        }
+
+        let parent = node.links.borrow().parent.upgrade();
+
+        let (left, right) = {
+            let mut links = node.links.borrow_mut();
+            links.parent = Weak::new();
+            (links.left.take(), links.right.take())
+        };
+        let merged = self.merge(left, right);
+
+        match parent {
+            Some(parent) => {
+                if let Some(child) = merged.as_ref() {
+                    child.set_parent(Rc::downgrade(&parent));
+                }
+
+                let mut links = parent.links.borrow_mut();
+                if is_same(&links.left, &node) {
+                    links.left = merged;
+                } else {
+                    links.right = merged;
+                }
+            }
+            None => self.root = merged,
+        }
+        self.size -= 1;
+
+        match Rc::try_unwrap(node) {
+            Ok(node) => Some(node.item),
+            _ => unreachable!(),
+        }
+    }
+
+    fn merge(&mut self, a: Link<T>, b: Link<T>) -> Link<T> {
+        let (mut top, mut other) = match (a, b) {
+            (None, b) => return b,
+            (a, None) => return a,
+            (Some(a), Some(b)) if b.item < a.item => (b, a),
+            (Some(a), Some(b)) => (a, b),
+        };
+        top.set_parent(Weak::new());
+        let root = Rc::clone(&top);
+
+        // walks down a random path of `top`, `other` being merged into the subtree below
+        loop {
+            let go_left = self.random.next_bool();
+
+            let next = {
+                let mut links = top.links.borrow_mut();
+                let slot = if go_left {
+                    &mut links.left
+                } else {
+                    &mut links.right
+                };
+
+                match slot.take() {
+                    None => {
+                        other.set_parent(Rc::downgrade(&top));
+                        *slot = Some(other);
+                        break;
+                    }
+                    Some(child) if other.item < child.item => {
+                        other.set_parent(Rc::downgrade(&top));
+                        *slot = Some(Rc::clone(&other));
+                        (Rc::clone(&other), child)
+                    }
+                    Some(child) => {
+                        *slot = Some(Rc::clone(&child));
+                        (child, other)
+                    }
+                }
+            };
+
+            top = next.0;
+            other = next.1;
+        }
+
+        Some(root)
+    }
+}
+
+fn is_same<T>(link: &Link<T>, node: &Rc<Node<T>>) -> bool {
+    match link {
+        Some(child) => Rc::ptr_eq(child, node),
+        None => false,
+    }
+}
+
+impl<T: Ord> Default for MeldableHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for MeldableHeap<T> {
+    // a random path can be long, so the nodes are not dropped recursively
+    fn drop(&mut self) {
+        let mut nodes: Vec<Rc<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(node) = nodes.pop() {
+            let mut links = node.links.borrow_mut();
+            nodes.extend(links.left.take());
+            nodes.extend(links.right.take());
+        }
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for MeldableHeap<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, x: T) {
+        self.insert(x);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.item)
+    }
+
+    fn remove_min(&mut self) -> Option<T> {
+        let root = self.root.as_ref()?;
+        let handle = Handle(Rc::downgrade(root));
+        self.remove(&handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MeldableHeap;
+    use crate::PriorityQueue;
+
+    #[test]
+    pub fn priority_queue() {
+        crate::tests::priority_queue(MeldableHeap::with_seed(1));
+    }
+
+    #[test]
+    pub fn meld_and_remove() {
+        let mut heap = MeldableHeap::with_seed(7);
+        let mut other = MeldableHeap::with_seed(8);
+
+        let mut handles = vec![];
+        for item in 0..50 {
+            if item % 2 == 0 {
+                handles.push(heap.insert(item));
+            } else {
+                handles.push(other.insert(item));
+            }
+        }
+
+        heap.meld(other);
+        assert_eq!(heap.size(), 50);
+
+        for item in (0..50).filter(|item| item % 3 == 0) {
+            assert_eq!(heap.remove(&handles[item]), Some(item));
+            assert_eq!(heap.remove(&handles[item]), None);
+        }
+        assert_eq!(heap.size(), 33);
+
+        let mut items = vec![];
+        while let Some(item) = heap.remove_min() {
+            items.push(item);
+        }
+        let expected: Vec<usize> = (0..50).filter(|item| item % 3 != 0).collect();
+        assert_eq!(items, expected);
+    }
+
+    #[test]
+    pub fn foreign_handle() {
+        let mut heap = MeldableHeap::with_seed(1);
+        let mut other = MeldableHeap::with_seed(2);
+        for item in 0..10 {
+            other.insert(item);
+        }
+        let handle = other.insert(5);
+        let root = other.insert(-1);
+        let kept = other.insert(7);
+
+        assert_eq!(heap.remove(&handle), None);
+        assert_eq!(heap.size(), 0);
+        heap.insert(3);
+        assert_eq!(heap.remove(&handle), None);
+        assert_eq!(heap.remove(&root), None);
+        assert_eq!(heap.size(), 1);
+        assert_eq!(other.size(), 13);
+
+        // the handles of a melded heap move with its items
+        let mut melded = MeldableHeap::with_seed(3);
+        melded.meld(other);
+        assert_eq!(heap.remove(&handle), None);
+        assert_eq!(melded.remove(&handle), Some(5));
+        assert_eq!(melded.remove(&root), Some(-1));
+        assert_eq!(melded.size(), 11);
+
+        heap.meld(melded);
+        assert_eq!(heap.remove(&kept), Some(7));
+        assert_eq!(heap.remove_min(), Some(0));
+        assert_eq!(heap.size(), 10);
+    }
+
+    #[test]
+    pub fn long_path() {
+        // every new least item becomes the root above the previous one
+        let mut heap = MeldableHeap::with_seed(1);
+        for item in (0..200_000).rev() {
+            heap.add(item);
+        }
+        for item in 0..3 {
+            assert_eq!(heap.remove_min(), Some(item));
+        }
+        assert_eq!(heap.size(), 199_997);
+        drop(heap);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::backend::{Array, Entry};
+use crate::{Deque, PriorityQueue};
+
+const DEFAULT_BACKEND_SIZE: usize = 2;
+const SIZE_UP_MULTIPLIER_NUMBER: usize = 2;
+const SIZE_DOWN_THRESHOLD: usize = 3;
+const SIZE_DOWN_DIVISION_NUMBER: usize = 2;
+
+/// A double-ended priority queue: items on even levels are smaller than their descendants,
+/// items on odd levels are greater.
+#[derive(Debug)]
+pub struct MinMaxHeap<T> {
+    backend: Array<T>,
+    size: usize,
+}
+
+impl<T: Ord> MinMaxHeap<T> {
+    pub fn new() -> MinMaxHeap<T> {
+        MinMaxHeap {
+            backend: Array::new(DEFAULT_BACKEND_SIZE),
+            size: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// O(log n).
+    pub fn add(&mut self, x: T) {
+        if self.is_size_up_required() {
+            self.size_up();
+        }
+
+        match self.backend.set(self.size, x) {
+            Ok(()) => (),
+            _ => unreachable!(),
+        }
+        self.size += 1;
+
+        self.bubble_up(self.size - 1);
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        if self.size == 0 {
+            return None;
+        }
+
+        Some(self.item(0))
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        let index = self.max_index()?;
+        Some(self.item(index))
+    }
+
+    /// O(log n).
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+
+        Some(self.remove_at(0))
+    }
+
+    /// O(log n).
+    pub fn pop_max(&mut self) -> Option<T> {
+        let index = self.max_index()?;
+        Some(self.remove_at(index))
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        match self.size {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ if self.item(2) > self.item(1) => Some(2),
+            _ => Some(1),
+        }
+    }
+
+    fn item(&self, index: usize) -> &T {
+        match self.backend.get(index) {
+            Some(Entry::Item(item)) => item,
+            _ => unreachable!(),
+        }
+    }
+
+    // on a min level, `a` goes before `b` when smaller, on a max level when greater
+    fn is_before(&self, a: usize, b: usize, is_min: bool) -> bool {
+        if is_min {
+            self.item(a) < self.item(b)
+        } else {
+            self.item(a) > self.item(b)
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> T {
+        self.size -= 1;
+        self.backend.swap(index, self.size);
+
+        let item = match self.backend.remove(self.size) {
+            Some(Entry::Item(item)) => item,
+            _ => unreachable!(),
+        };
+
+        if index < self.size {
+            self.trickle_down(index);
+        }
+
+        if self.is_size_down_required() {
+            self.size_down();
+        }
+
+        item
+    }
+
+    fn bubble_up(&mut self, index: usize) {
+        if index == 0 {
+            return;
+        }
+
+        let is_min = is_min_level(index);
+        let parent = (index - 1) / 2;
+        if self.is_before(parent, index, is_min) {
+            self.backend.swap(index, parent);
+            self.bubble_up_grandparents(parent, !is_min);
+        } else {
+            self.bubble_up_grandparents(index, is_min);
+        }
+    }
+
+    fn bubble_up_grandparents(&mut self, mut index: usize, is_min: bool) {
+        while index > 2 {
+            let grandparent = ((index - 1) / 2 - 1) / 2;
+            if !self.is_before(index, grandparent, is_min) {
+                break;
+            }
+            self.backend.swap(index, grandparent);
+            index = grandparent;
+        }
+    }
+
+    fn trickle_down(&mut self, mut index: usize) {
+        let is_min = is_min_level(index);
+
+        loop {
+            let first_child = 2 * index + 1;
+            if first_child >= self.size {
+                return;
+            }
+
+            // the first among the children and the grandchildren
+            let mut first = first_child;
+            let descendants = [
+                first_child + 1,
+                2 * first_child + 1,
+                2 * first_child + 2,
+                2 * first_child + 3,
+                2 * first_child + 4,
+            ];
+            for descendant in descendants.iter().copied() {
+                if descendant < self.size && self.is_before(descendant, first, is_min) {
+                    first = descendant;
+                }
+            }
+
+            if !self.is_before(first, index, is_min) {
+                return;
+            }
+            self.backend.swap(first, index);
+
+            if first <= first_child + 1 {
+                return;
+            }
+
+            let parent = (first - 1) / 2;
+            if self.is_before(parent, first, is_min) {
+                self.backend.swap(first, parent);
+            }
+            index = first;
+        }
+    }
+}
+
+fn is_min_level(index: usize) -> bool {
+    let level = usize::BITS - 1 - (index + 1).leading_zeros();
+    level & 1 == 0
+}
+
+impl<T: Ord> Default for MinMaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for MinMaxHeap<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, x: T) {
+        MinMaxHeap::add(self, x);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_min()
+    }
+
+    fn remove_min(&mut self) -> Option<T> {
+        self.pop_min()
+    }
+}
+
+/// Both ends are the sorted order of the items: `add_first` and `add_last` only add `x`,
+/// `remove_first` removes the minimum and `remove_last` the maximum.
+impl<T: Ord> Deque<T> for MinMaxHeap<T> {
+    fn add_first(&mut self, x: T) {
+        self.add(x);
+    }
+
+    fn add_last(&mut self, x: T) {
+        self.add(x);
+    }
+
+    fn remove_first(&mut self) -> Option<T> {
+        self.pop_min()
+    }
+
+    fn remove_last(&mut self) -> Option<T> {
+        self.pop_max()
+    }
+}
+
+impl<T> MinMaxHeap<T> {
+    fn backend_len(&self) -> usize {
+        self.backend.len()
+    }
+
+    fn is_size_up_required(&self) -> bool {
+        self.size == self.backend_len()
+    }
+
+    fn is_size_down_required(&self) -> bool {
+        self.size * SIZE_DOWN_THRESHOLD < self.backend_len()
+            && self.backend_len() > DEFAULT_BACKEND_SIZE
+    }
+
+    fn size_up(&mut self) {
+        self.backend
+            .resize(self.backend_len() * SIZE_UP_MULTIPLIER_NUMBER, 0, self.size);
+    }
+
+    fn size_down(&mut self) {
+        self.backend
+            .resize(self.backend_len() / SIZE_DOWN_DIVISION_NUMBER, 0, self.size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MinMaxHeap;
+    use crate::Deque;
+
+    #[test]
+    pub fn priority_queue() {
+        crate::tests::priority_queue(MinMaxHeap::new());
+    }
+
+    #[test]
+    pub fn double_ended() {
+        let mut heap = MinMaxHeap::new();
+        assert_eq!(heap.peek_max(), None);
+        assert_eq!(heap.pop_max(), None);
+
+        for item in [8, 3, 9, 1, 7, 2, 6, 4, 5, 0].iter() {
+            heap.add(*item);
+        }
+        assert_eq!(heap.peek_min(), Some(&0));
+        assert_eq!(heap.peek_max(), Some(&9));
+
+        let mut items = vec![];
+        while heap.size() > 0 {
+            items.push(heap.pop_max().unwrap());
+            if let Some(item) = heap.pop_min() {
+                items.push(item);
+            }
+        }
+        assert_eq!(items, vec![9, 0, 8, 1, 7, 2, 6, 3, 5, 4]);
+    }
+
+    #[test]
+    pub fn deque() {
+        let mut heap = MinMaxHeap::new();
+
+        // a bounded buffer keeping the 3 smallest items
+        for item in [5, 9, 2, 7, 1, 8].iter() {
+            heap.add_last(*item);
+            if heap.size() > 3 {
+                heap.remove_last();
+            }
+        }
+
+        assert_eq!(heap.remove_first(), Some(1));
+        assert_eq!(heap.remove_last(), Some(5));
+        assert_eq!(heap.remove_first(), Some(2));
+        assert_eq!(heap.remove_last(), None);
+    }
+}
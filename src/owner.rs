@@ -0,0 +1,51 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// An id returned once per process, for the heaps that are never melded.
+pub(in crate) fn unique_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Identifies the heap a node belongs to. Melding a heap forwards its owner to the owner of the
+/// heap it is melded into, the forwarding chains being shortened on every lookup.
+#[derive(Debug, Clone)]
+pub(in crate) struct Owner(Rc<RefCell<Option<Owner>>>);
+
+impl Owner {
+    pub(in crate) fn new() -> Owner {
+        Owner(Rc::new(RefCell::new(None)))
+    }
+
+    /// Hands the nodes of `self` over to `owner`.
+    pub(in crate) fn forward_to(&self, owner: &Owner) {
+        *self.0.borrow_mut() = Some(owner.clone());
+    }
+
+    /// Whether a node of `self` belongs to `owner` by now. O(log n) amortized over n melds.
+    pub(in crate) fn is(&self, owner: &Owner) -> bool {
+        Rc::ptr_eq(&self.resolve().0, &owner.0)
+    }
+
+    fn resolve(&self) -> Owner {
+        let mut root = self.clone();
+        loop {
+            let next = root.0.borrow().clone();
+            match next {
+                Some(next) => root = next,
+                None => break,
+            }
+        }
+
+        let mut current = self.clone();
+        while !Rc::ptr_eq(&current.0, &root.0) {
+            current = match current.0.replace(Some(root.clone())) {
+                Some(next) => next,
+                None => unreachable!(),
+            };
+        }
+        root
+    }
+}
@@ -0,0 +1,388 @@
+use crate::owner::Owner;
+use crate::CollectionError;
+use crate::{AddressablePriorityQueue, PriorityQueue};
+use crate::{INVALID_HANDLE_ERROR, INVALID_KEY_ERROR};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+type NodeRef<T> = Rc<RefCell<Node<T>>>;
+type Link<T> = Option<NodeRef<T>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    item: Rc<T>,
+    owner: Owner,
+    // the previous sibling, or the parent of a first child
+    prev: Weak<RefCell<Node<T>>>,
+    next: Link<T>,
+    child: Link<T>,
+}
+
+impl<T> Node<T> {
+    fn new(item: T, owner: Owner) -> NodeRef<T> {
+        Rc::new(RefCell::new(Node {
+            item: Rc::new(item),
+            owner,
+            prev: Weak::new(),
+            next: None,
+            child: None,
+        }))
+    }
+}
+
+/// Refers to an item of a heap, stays valid when the heap is melded into another one.
+pub struct Handle<T>(Weak<RefCell<Node<T>>>);
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle")
+    }
+}
+
+/// Handles of another heap are rejected, those of a heap melded into this one stay valid.
+#[derive(Debug)]
+pub struct PairingHeap<T> {
+    root: Link<T>,
+    // the item of the root, lent out by `peek`
+    min: Option<Rc<T>>,
+    size: usize,
+    owner: Owner,
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> PairingHeap<T> {
+        PairingHeap {
+            root: None,
+            min: None,
+            size: 0,
+            owner: Owner::new(),
+        }
+    }
+
+    /// Moves all the items of `other` into this heap. O(1).
+    pub fn meld(&mut self, mut other: PairingHeap<T>) {
+        let root = merge(self.root.take(), other.root.take());
+        self.set_root(root);
+        self.size += other.size;
+        other.owner.forward_to(&self.owner);
+    }
+
+    // the node of `handle`, unless it belongs to another heap or was removed
+    fn node(&self, handle: &Handle<T>) -> Option<NodeRef<T>> {
+        let node = handle.0.upgrade()?;
+        if node.borrow().owner.is(&self.owner) {
+            Some(node)
+        } else {
+            None
+        }
+    }
+
+    fn set_root(&mut self, root: Link<T>) {
+        self.min = root.as_ref().map(|node| Rc::clone(&node.borrow().item));
+        self.root = root;
+    }
+
+    // takes the subtree of `node` out of the heap
+    fn detach(&mut self, node: &NodeRef<T>) {
+        let prev = node.borrow().prev.upgrade();
+        match prev {
+            Some(prev) => {
+                let next = {
+                    let mut links = node.borrow_mut();
+                    links.prev = Weak::new();
+                    links.next.take()
+                };
+                if let Some(next) = next.as_ref() {
+                    next.borrow_mut().prev = Rc::downgrade(&prev);
+                }
+
+                let mut links = prev.borrow_mut();
+                if is_same(&links.child, node) {
+                    links.child = next;
+                } else {
+                    links.next = next;
+                }
+            }
+            None if is_same(&self.root, node) => self.root = None,
+            None => unreachable!(),
+        }
+    }
+}
+
+fn is_same<T>(link: &Link<T>, node: &NodeRef<T>) -> bool {
+    match link {
+        Some(other) => Rc::ptr_eq(other, node),
+        None => false,
+    }
+}
+
+// makes the larger of two roots the first child of the other one
+fn link<T: Ord>(a: NodeRef<T>, b: NodeRef<T>) -> NodeRef<T> {
+    let (parent, child) = if b.borrow().item < a.borrow().item {
+        (b, a)
+    } else {
+        (a, b)
+    };
+
+    {
+        let mut parent_links = parent.borrow_mut();
+        let mut child_links = child.borrow_mut();
+        if let Some(first) = parent_links.child.take() {
+            first.borrow_mut().prev = Rc::downgrade(&child);
+            child_links.next = Some(first);
+        }
+        child_links.prev = Rc::downgrade(&parent);
+    }
+    parent.borrow_mut().child = Some(child);
+
+    parent
+}
+
+fn merge<T: Ord>(a: Link<T>, b: Link<T>) -> Link<T> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(a), Some(b)) => Some(link(a, b)),
+    }
+}
+
+// two-pass pairing of a list of siblings into a single tree
+fn combine<T: Ord>(first: Link<T>) -> Link<T> {
+    let mut siblings = vec![];
+    let mut current = first;
+    while let Some(node) = current {
+        {
+            let mut links = node.borrow_mut();
+            links.prev = Weak::new();
+            current = links.next.take();
+        }
+        siblings.push(node);
+    }
+
+    let mut pairs = vec![];
+    let mut siblings = siblings.into_iter();
+    while let Some(a) = siblings.next() {
+        match siblings.next() {
+            Some(b) => pairs.push(link(a, b)),
+            None => pairs.push(a),
+        }
+    }
+
+    let mut tree = pairs.pop();
+    while let Some(node) = pairs.pop() {
+        tree = merge(Some(node), tree);
+    }
+    tree
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for PairingHeap<T> {
+    // sibling lists can be long, so the nodes are not dropped recursively
+    fn drop(&mut self) {
+        let mut nodes: Vec<NodeRef<T>> = self.root.take().into_iter().collect();
+        while let Some(node) = nodes.pop() {
+            let mut links = node.borrow_mut();
+            nodes.extend(links.child.take());
+            nodes.extend(links.next.take());
+        }
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for PairingHeap<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    /// O(1).
+    fn add(&mut self, x: T) {
+        self.insert(x);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.min.as_deref()
+    }
+
+    /// O(log n) amortized.
+    fn remove_min(&mut self) -> Option<T> {
+        let root = self.root.as_ref()?;
+        let handle = Handle(Rc::downgrade(root));
+        self.remove(&handle)
+    }
+}
+
+impl<T: Ord> AddressablePriorityQueue<T> for PairingHeap<T> {
+    type Handle = Handle<T>;
+
+    /// O(1).
+    fn insert(&mut self, x: T) -> Handle<T> {
+        let node = Node::new(x, self.owner.clone());
+        let handle = Handle(Rc::downgrade(&node));
+
+        let root = merge(self.root.take(), Some(node));
+        self.set_root(root);
+        self.size += 1;
+
+        handle
+    }
+
+    fn contains(&self, handle: &Handle<T>) -> bool {
+        self.node(handle).is_some()
+    }
+
+    /// O(log n) amortized, cheap in practice.
+    fn decrease_key(&mut self, handle: &Handle<T>, x: T) -> Result<(), CollectionError> {
+        let node = self.node(handle).ok_or(INVALID_HANDLE_ERROR)?;
+        if x > *node.borrow().item {
+            return Err(INVALID_KEY_ERROR);
+        }
+
+        self.detach(&node);
+        node.borrow_mut().item = Rc::new(x);
+
+        let root = merge(self.root.take(), Some(node));
+        self.set_root(root);
+        Ok(())
+    }
+
+    /// O(log n) amortized.
+    fn increase_key(&mut self, handle: &Handle<T>, x: T) -> Result<(), CollectionError> {
+        let node = self.node(handle).ok_or(INVALID_HANDLE_ERROR)?;
+        if x < *node.borrow().item {
+            return Err(INVALID_KEY_ERROR);
+        }
+
+        self.detach(&node);
+        let children = {
+            let mut links = node.borrow_mut();
+            links.item = Rc::new(x);
+            links.child.take()
+        };
+
+        let root = merge(self.root.take(), combine(children));
+        let root = merge(root, Some(node));
+        self.set_root(root);
+        Ok(())
+    }
+
+    /// O(log n) amortized.
+    fn remove(&mut self, handle: &Handle<T>) -> Option<T> {
+        let node = self.node(handle)?;
+        self.detach(&node);
+
+        let children = node.borrow_mut().child.take();
+        let root = merge(self.root.take(), combine(children));
+        self.set_root(root);
+        self.size -= 1;
+
+        let item = match Rc::try_unwrap(node) {
+            Ok(node) => node.into_inner().item,
+            _ => unreachable!(),
+        };
+        match Rc::try_unwrap(item) {
+            Ok(item) => Some(item),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Handle, NodeRef, PairingHeap};
+    use crate::{AddressablePriorityQueue, PriorityQueue};
+    use std::rc::Rc;
+
+    #[test]
+    pub fn priority_queue() {
+        crate::tests::priority_queue(PairingHeap::new());
+    }
+
+    #[test]
+    pub fn addressable() {
+        crate::tests::addressable(PairingHeap::new());
+    }
+
+    #[test]
+    pub fn meld() {
+        crate::tests::meld(PairingHeap::new(), PairingHeap::new(), PairingHeap::meld);
+    }
+
+    #[test]
+    pub fn foreign_handle() {
+        crate::tests::foreign_handle(PairingHeap::new(), PairingHeap::new());
+    }
+
+    fn children(node: &NodeRef<u32>) -> Vec<NodeRef<u32>> {
+        let mut children = vec![];
+        let mut child = node.borrow().child.clone();
+        while let Some(current) = child {
+            child = current.borrow().next.clone();
+            children.push(current);
+        }
+        children
+    }
+
+    fn items(nodes: &[NodeRef<u32>]) -> Vec<u32> {
+        nodes.iter().map(|node| *node.borrow().item).collect()
+    }
+
+    // checks the heap order and the `prev` links below `node`, returns the number of nodes
+    fn check(node: &NodeRef<u32>) -> usize {
+        let mut size = 1;
+        let mut prev = Rc::clone(node);
+        for child in children(node) {
+            assert!(child.borrow().item >= node.borrow().item);
+            assert!(Rc::ptr_eq(&child.borrow().prev.upgrade().unwrap(), &prev));
+            size += check(&child);
+            prev = child;
+        }
+        size
+    }
+
+    #[test]
+    pub fn two_pass() {
+        let mut heap = PairingHeap::new();
+        for item in 1..=8 {
+            heap.add(item);
+        }
+        // each item becomes the first child of the root
+        {
+            let root = heap.root.as_ref().unwrap();
+            assert_eq!(items(&children(root)), vec![8, 7, 6, 5, 4, 3, 2]);
+            assert_eq!(check(root), 8);
+        }
+
+        // (8, 7), (6, 5), (4, 3) are paired left to right, then merged right to left into 2
+        assert_eq!(heap.remove_min(), Some(1));
+        let handle = {
+            let root = heap.root.as_ref().unwrap();
+            assert_eq!(*root.borrow().item, 2);
+            let nodes = children(root);
+            assert_eq!(items(&nodes), vec![7, 5, 3]);
+            for (node, item) in nodes.iter().zip([8, 6, 4].iter()) {
+                assert_eq!(items(&children(node)), vec![*item]);
+            }
+            assert_eq!(check(root), 7);
+            Handle(Rc::downgrade(&nodes[0]))
+        };
+
+        // the children of a node whose key grows are paired back into the heap
+        assert_eq!(heap.increase_key(&handle, 9), Ok(()));
+        for item in (2..=9).filter(|item| *item != 7) {
+            assert_eq!(check(heap.root.as_ref().unwrap()), heap.size());
+            assert_eq!(heap.remove_min(), Some(item));
+        }
+        assert!(heap.root.is_none());
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+// splitmix64, good enough for randomized structures and any seed including 0
+#[derive(Debug)]
+pub(in crate) struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub(in crate) fn new() -> Random {
+        Self::with_seed(RandomState::new().build_hasher().finish())
+    }
+
+    pub(in crate) const fn with_seed(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    pub(in crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub(in crate) fn next_bool(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+
+    /// A number in `0..n`, `n` must not be 0.
+    pub(in crate) fn next_below(&mut self, n: usize) -> usize {
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Random;
+
+    #[test]
+    pub fn seeded() {
+        let mut a = Random::with_seed(42);
+        let mut b = Random::with_seed(42);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+
+        let heads = (0..1000).filter(|_| a.next_bool()).count();
+        assert!(heads > 400 && heads < 600);
+
+        for n in 1..100 {
+            assert!(a.next_below(n) < n);
+        }
+    }
+}
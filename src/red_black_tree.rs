@@ -0,0 +1,503 @@
+use crate::binary_search_tree::{self, SearchNode};
+use crate::{Range, SSet};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::RangeBounds;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+type Tree<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    item: T,
+    color: Color,
+    left: Tree<T>,
+    right: Tree<T>,
+}
+
+impl<T> Node<T> {
+    fn new(item: T) -> Box<Node<T>> {
+        Box::new(Node {
+            item,
+            color: Color::Red,
+            left: None,
+            right: None,
+        })
+    }
+}
+
+impl<T> SearchNode for Node<T> {
+    type Item = T;
+
+    fn item(&self) -> &T {
+        &self.item
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+}
+
+/// A left-leaning red-black tree, of height at most 2 log2(n + 1): `add`, `take`, `find`,
+/// `successor`, `predecessor` and the ends take O(log n), `range` O(log n + k) for k items.
+#[derive(Debug)]
+pub struct RedBlackTree<T> {
+    root: Tree<T>,
+    size: usize,
+}
+
+impl<T> RedBlackTree<T> {
+    pub fn new() -> RedBlackTree<T> {
+        RedBlackTree {
+            root: None,
+            size: 0,
+        }
+    }
+}
+
+impl<T> Default for RedBlackTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> SSet<T> for RedBlackTree<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, item: T) -> bool {
+        let (mut root, added) = insert(self.root.take(), item);
+        root.color = Color::Black;
+        self.root = Some(root);
+
+        if added {
+            self.size += 1;
+        }
+        added
+    }
+
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let root = self.root.take()?;
+        let (mut root, removed, _) = delete(root, item);
+        set_color(&mut root, Color::Black);
+        self.root = root;
+
+        if removed.is_some() {
+            self.size -= 1;
+        }
+        removed
+    }
+
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        binary_search_tree::find(self.root.as_deref(), item)
+    }
+
+    fn successor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        binary_search_tree::successor(self.root.as_deref(), item)
+    }
+
+    fn predecessor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        binary_search_tree::predecessor(self.root.as_deref(), item)
+    }
+
+    fn first(&self) -> Option<&T> {
+        binary_search_tree::first(self.root.as_deref())
+    }
+
+    fn last(&self) -> Option<&T> {
+        binary_search_tree::last(self.root.as_deref())
+    }
+
+    fn pop_first(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let (mut root, min, _) = delete_min(root);
+        set_color(&mut root, Color::Black);
+        self.root = root;
+        self.size -= 1;
+
+        Some(min)
+    }
+
+    fn pop_last(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let (mut root, max, _) = delete_max(root);
+        set_color(&mut root, Color::Black);
+        self.root = root;
+        self.size -= 1;
+
+        Some(max)
+    }
+
+    fn range<Q, R>(&self, bounds: R) -> Range<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        binary_search_tree::range(self.root.as_deref(), bounds)
+    }
+}
+
+fn is_red<T>(tree: &Tree<T>) -> bool {
+    match tree {
+        Some(node) => node.color == Color::Red,
+        None => false,
+    }
+}
+
+fn has_red_child<T>(tree: &Tree<T>) -> bool {
+    match tree {
+        Some(node) => is_red(&node.left) || is_red(&node.right),
+        None => false,
+    }
+}
+
+fn set_color<T>(tree: &mut Tree<T>, color: Color) {
+    if let Some(node) = tree.as_mut() {
+        node.color = color;
+    }
+}
+
+// the child moves up and takes over the color of the node, the node turns red
+fn rotate_left<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut right = node
+        .right
+        .take()
+        .expect("rotate left requires a right child");
+    node.right = right.left.take();
+    right.color = node.color;
+    node.color = Color::Red;
+    right.left = Some(node);
+    right
+}
+
+fn rotate_right<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut left = node
+        .left
+        .take()
+        .expect("rotate right requires a left child");
+    node.left = left.right.take();
+    left.color = node.color;
+    node.color = Color::Red;
+    left.right = Some(node);
+    left
+}
+
+fn push_black<T>(node: &mut Node<T>) {
+    node.color = Color::Red;
+    set_color(&mut node.left, Color::Black);
+    set_color(&mut node.right, Color::Black);
+}
+
+// a red right child is only allowed next to a red left child
+fn lean_left<T>(node: Box<Node<T>>) -> Box<Node<T>> {
+    if is_red(&node.right) && !is_red(&node.left) {
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+fn insert<T: Ord>(tree: Tree<T>, item: T) -> (Box<Node<T>>, bool) {
+    let mut node = match tree {
+        Some(node) => node,
+        None => return (Node::new(item), true),
+    };
+
+    let added = match item.cmp(node.item.borrow()) {
+        Ordering::Less => {
+            let (left, added) = insert(node.left.take(), item);
+            node.left = Some(left);
+            added
+        }
+        Ordering::Greater => {
+            let (right, added) = insert(node.right.take(), item);
+            node.right = Some(right);
+            added
+        }
+        Ordering::Equal => return (node, false),
+    };
+
+    (add_fix_up(node), added)
+}
+
+fn add_fix_up<T>(node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut node = lean_left(node);
+
+    if is_red(&node.left) && has_red_child(&node.left) {
+        // a 5-node is split by pushing the black down, otherwise it becomes a 4-node
+        if is_red(&node.right) {
+            push_black(&mut node);
+        } else {
+            node = rotate_right(node);
+        }
+    } else if is_red(&node.right) && has_red_child(&node.right) {
+        push_black(&mut node);
+    }
+
+    node
+}
+
+// the third value is true when the black height of the returned tree has decreased
+fn delete<T, Q>(mut node: Box<Node<T>>, item: &Q) -> (Tree<T>, Option<T>, bool)
+where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    match item.cmp(node.item.borrow()) {
+        Ordering::Less => {
+            let left = match node.left.take() {
+                Some(left) => left,
+                None => return (Some(node), None, false),
+            };
+            let (left, removed, short) = delete(left, item);
+            node.left = left;
+            let (node, short) = if short {
+                fix_left_short(node)
+            } else {
+                (node, false)
+            };
+            (Some(lean_left(node)), removed, short)
+        }
+        Ordering::Greater => {
+            let right = match node.right.take() {
+                Some(right) => right,
+                None => return (Some(node), None, false),
+            };
+            let (right, removed, short) = delete(right, item);
+            node.right = right;
+            let (node, short) = if short {
+                fix_right_short(node)
+            } else {
+                (node, false)
+            };
+            (Some(lean_left(node)), removed, short)
+        }
+        Ordering::Equal => match node.right.take() {
+            None => {
+                // only a red leaf can hang below a node without a right child
+                let short = node.color == Color::Black && node.left.is_none();
+                let mut left = node.left.take();
+                set_color(&mut left, Color::Black);
+                (left, Some(node.item), short)
+            }
+            Some(right) => {
+                let (right, min, short) = delete_min(right);
+                node.right = right;
+                let removed = std::mem::replace(&mut node.item, min);
+                let (node, short) = if short {
+                    fix_right_short(node)
+                } else {
+                    (node, false)
+                };
+                (Some(lean_left(node)), Some(removed), short)
+            }
+        },
+    }
+}
+
+fn delete_min<T>(mut node: Box<Node<T>>) -> (Tree<T>, T, bool) {
+    let left = match node.left.take() {
+        Some(left) => left,
+        // the minimum is always a leaf
+        None => return (None, node.item, node.color == Color::Black),
+    };
+
+    let (left, min, short) = delete_min(left);
+    node.left = left;
+    let (node, short) = if short {
+        fix_left_short(node)
+    } else {
+        (node, false)
+    };
+    (Some(lean_left(node)), min, short)
+}
+
+fn delete_max<T>(mut node: Box<Node<T>>) -> (Tree<T>, T, bool) {
+    let right = match node.right.take() {
+        Some(right) => right,
+        None => {
+            let short = node.color == Color::Black && node.left.is_none();
+            let mut left = node.left.take();
+            set_color(&mut left, Color::Black);
+            return (left, node.item, short);
+        }
+    };
+
+    let (right, max, short) = delete_max(right);
+    node.right = right;
+    let (node, short) = if short {
+        fix_right_short(node)
+    } else {
+        (node, false)
+    };
+    (Some(lean_left(node)), max, short)
+}
+
+fn fix_left_short<T>(mut node: Box<Node<T>>) -> (Box<Node<T>>, bool) {
+    if is_red(&node.left) {
+        set_color(&mut node.left, Color::Black);
+        return (node, false);
+    }
+
+    // the left child is black, so the sibling is black too
+    let mut sibling = node.right.take().expect("sibling of a short tree exists");
+    if !is_red(&sibling.left) {
+        let short = node.color == Color::Black;
+        sibling.color = Color::Red;
+        node.color = Color::Black;
+        node.right = Some(sibling);
+        return (rotate_left(node), short);
+    }
+
+    let mut middle = sibling.left.take().expect("red left child exists");
+    sibling.left = middle.right.take();
+    sibling.color = Color::Black;
+    node.right = middle.left.take();
+    middle.color = node.color;
+    node.color = Color::Black;
+    middle.left = Some(lean_left(node));
+    middle.right = Some(lean_left(sibling));
+    (middle, false)
+}
+
+fn fix_right_short<T>(mut node: Box<Node<T>>) -> (Box<Node<T>>, bool) {
+    if is_red(&node.right) {
+        set_color(&mut node.right, Color::Black);
+        return (node, false);
+    }
+
+    if is_red(&node.left) {
+        let mut top = rotate_right(node);
+        let (right, _) = fix_right_short(top.right.take().expect("rotated node exists"));
+        top.right = Some(right);
+        return (lean_left(top), false);
+    }
+
+    let sibling = node.left.as_mut().expect("sibling of a short tree exists");
+    if !is_red(&sibling.left) {
+        let short = node.color == Color::Black;
+        sibling.color = Color::Red;
+        node.color = Color::Black;
+        return (node, short);
+    }
+
+    let mut top = rotate_right(node);
+    set_color(&mut top.left, Color::Black);
+    set_color(&mut top.right, Color::Black);
+    (top, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, RedBlackTree, Tree};
+    use crate::SSet;
+
+    fn black_height<T: Ord>(tree: &Tree<T>, parent_red: bool) -> usize {
+        match tree {
+            None => 1,
+            Some(node) => {
+                let red = node.color == Color::Red;
+                assert!(!(parent_red && red), "red node with red parent");
+                assert!(
+                    !super::is_red(&node.right) || super::is_red(&node.left),
+                    "right-leaning red edge"
+                );
+                if let Some(left) = &node.left {
+                    assert!(left.item < node.item);
+                }
+                if let Some(right) = &node.right {
+                    assert!(right.item > node.item);
+                }
+
+                let left = black_height(&node.left, red);
+                let right = black_height(&node.right, red);
+                assert_eq!(left, right, "unbalanced black height");
+
+                if red {
+                    left
+                } else {
+                    left + 1
+                }
+            }
+        }
+    }
+
+    fn check<T: Ord>(tree: &RedBlackTree<T>) {
+        assert!(!super::is_red(&tree.root));
+        black_height(&tree.root, false);
+    }
+
+    #[test]
+    pub fn sset() {
+        crate::tests::sset(RedBlackTree::new());
+    }
+
+    #[test]
+    pub fn range() {
+        crate::tests::range(RedBlackTree::new());
+    }
+
+    #[test]
+    pub fn entry() {
+        crate::tests::entry(RedBlackTree::new());
+    }
+
+    #[test]
+    pub fn balanced() {
+        let mut tree = RedBlackTree::new();
+
+        let mut value: u64 = 7;
+        for _ in 0..2000 {
+            value = value
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let item = (value >> 33) % 500;
+            if (value >> 20) % 3 == 1 {
+                tree.remove(&item);
+            } else {
+                tree.add(item);
+            }
+            check(&tree);
+        }
+
+        // the least and the greatest leaves, then every other node
+        for _ in 0..50 {
+            tree.pop_first();
+            check(&tree);
+            tree.pop_last();
+            check(&tree);
+        }
+        for item in 0..500 {
+            tree.remove(&item);
+            check(&tree);
+        }
+        assert_eq!(tree.size(), 0);
+    }
+}
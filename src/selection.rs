@@ -0,0 +1,245 @@
+//! Order statistics over any `List` implementor without sorting it.
+//!
+//! The selection rearranges the list: after `select_nth(list, k)`, the items before `k` are
+//! smaller or equal to the item at `k` and the items after it are greater or equal.
+
+use crate::random::Random;
+use crate::sorting::{partition, quick_sort_by, quick_sort_range, Items, ListItems};
+use crate::List;
+use std::cmp::Ordering;
+
+const GROUP_SIZE: usize = 5;
+
+/// How `select_nth_with` picks its pivots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pivot {
+    /// Expected O(n).
+    Random,
+    /// O(n) in the worst case, with a larger constant.
+    MedianOfMedians,
+}
+
+/// The `k`-th smallest item, counting from 0, in expected O(n). `None` when `k` is out of bound.
+pub fn select_nth<T: Ord, L: List<T> + ?Sized>(list: &mut L, k: usize) -> Option<&T> {
+    select_nth_with(list, k, Pivot::Random)
+}
+
+pub fn select_nth_with<T: Ord, L: List<T> + ?Sized>(
+    list: &mut L,
+    k: usize,
+    pivot: Pivot,
+) -> Option<&T> {
+    if k >= list.size() {
+        return None;
+    }
+
+    let mut items = ListItems::new(list);
+    let mut random = Random::new();
+    let size = items.len();
+    select_range(&mut items, 0, size, k, pivot, &mut random, &mut T::cmp);
+
+    list.get(k)
+}
+
+/// The lower median. Expected O(n).
+pub fn median<T: Ord, L: List<T> + ?Sized>(list: &mut L) -> Option<&T> {
+    if list.size() == 0 {
+        return None;
+    }
+
+    let k = (list.size() - 1) / 2;
+    select_nth(list, k)
+}
+
+/// Moves the `k` greatest items to the end of the list, in ascending order, and returns the
+/// index of the first one. Expected O(n + k log k).
+pub fn top_k<T: Ord, L: List<T> + ?Sized>(list: &mut L, k: usize) -> usize {
+    let size = list.size();
+    if k == 0 {
+        return size;
+    }
+    if k >= size {
+        quick_sort_by(list, T::cmp);
+        return 0;
+    }
+
+    let from = size - k;
+    let mut items = ListItems::new(list);
+    let mut random = Random::new();
+    select_range(
+        &mut items,
+        0,
+        size,
+        from,
+        Pivot::Random,
+        &mut random,
+        &mut T::cmp,
+    );
+    quick_sort_range(&mut items, from, size, &mut T::cmp, &mut random);
+
+    from
+}
+
+// narrows `from..to` down to the equal items around `k`
+fn select_range<T, I, F>(
+    items: &mut I,
+    mut from: usize,
+    mut to: usize,
+    k: usize,
+    pivot: Pivot,
+    random: &mut Random,
+    compare: &mut F,
+) where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    while to - from > 1 {
+        let index = match pivot {
+            Pivot::Random => from + random.next_below(to - from),
+            Pivot::MedianOfMedians => median_of_medians(items, from, to, random, compare),
+        };
+        items.swap(from, index);
+
+        let (less, greater) = partition(items, from, to, compare);
+        if k < less {
+            to = less;
+        } else if k >= greater {
+            from = greater;
+        } else {
+            return;
+        }
+    }
+}
+
+// gathers the medians of groups of 5 at the start of the range and selects their median,
+// which has at least 3/10 of the items on each side
+fn median_of_medians<T, I, F>(
+    items: &mut I,
+    from: usize,
+    to: usize,
+    random: &mut Random,
+    compare: &mut F,
+) -> usize
+where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if to - from <= GROUP_SIZE {
+        insertion_sort(items, from, to, compare);
+        return from + (to - from - 1) / 2;
+    }
+
+    let mut medians = from;
+    let mut group = from;
+    while group < to {
+        let group_end = std::cmp::min(group + GROUP_SIZE, to);
+        insertion_sort(items, group, group_end, compare);
+        items.swap(medians, group + (group_end - group - 1) / 2);
+
+        medians += 1;
+        group = group_end;
+    }
+
+    let k = from + (medians - from - 1) / 2;
+    select_range(
+        items,
+        from,
+        medians,
+        k,
+        Pivot::MedianOfMedians,
+        random,
+        compare,
+    );
+    k
+}
+
+fn insertion_sort<T, I, F>(items: &mut I, from: usize, to: usize, compare: &mut F)
+where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for end in from + 1..to {
+        let mut index = end;
+        while index > from && compare(items.item(index), items.item(index - 1)) == Ordering::Less {
+            items.swap(index, index - 1);
+            index -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{median, select_nth, select_nth_with, top_k, Pivot};
+    use crate::array_deque::ArrayDeque;
+    use crate::array_stack::ArrayStack;
+    use crate::{Deque, List, Stack};
+
+    fn items(count: usize) -> Vec<u64> {
+        let mut state = 7u64;
+        (0..count)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) % 100
+            })
+            .collect()
+    }
+
+    fn to_deque(items: &[u64]) -> ArrayDeque<u64> {
+        let mut deque = ArrayDeque::new();
+        for item in items.iter() {
+            deque.add_last(*item);
+        }
+        deque
+    }
+
+    #[test]
+    pub fn select() {
+        let items = items(200);
+        let mut sorted = items.clone();
+        sorted.sort();
+
+        for pivot in [Pivot::Random, Pivot::MedianOfMedians].iter() {
+            for k in (0..200).filter(|k| k % 7 == 1) {
+                let mut deque = to_deque(&items);
+                assert_eq!(select_nth_with(&mut deque, k, *pivot), Some(&sorted[k]));
+
+                let nth = sorted[k];
+                assert!((0..k).all(|index| *deque.get(index).unwrap() <= nth));
+                assert!((k..200).all(|index| *deque.get(index).unwrap() >= nth));
+            }
+        }
+
+        let mut deque = to_deque(&items);
+        assert_eq!(select_nth(&mut deque, 199), sorted.last());
+        assert_eq!(select_nth(&mut deque, 200), None);
+
+        // sorted input is the worst case of a fixed pivot
+        let mut deque = to_deque(&sorted);
+        assert_eq!(
+            select_nth_with(&mut deque, 50, Pivot::MedianOfMedians),
+            Some(&sorted[50])
+        );
+    }
+
+    #[test]
+    pub fn median_and_top_k() {
+        let mut stack = ArrayStack::new();
+        assert_eq!(median(&mut stack), None);
+
+        for item in [5, 1, 4, 2, 3, 6].iter() {
+            stack.push(*item);
+        }
+        assert_eq!(median(&mut stack), Some(&3));
+
+        assert_eq!(top_k(&mut stack, 3), 3);
+        let top: Vec<i32> = (3..6).map(|index| *stack.get(index).unwrap()).collect();
+        assert_eq!(top, vec![4, 5, 6]);
+
+        assert_eq!(top_k(&mut stack, 0), 6);
+        assert_eq!(top_k(&mut stack, 10), 0);
+        let all: Vec<i32> = (0..6).map(|index| *stack.get(index).unwrap()).collect();
+        assert_eq!(all, vec![1, 2, 3, 4, 5, 6]);
+    }
+}
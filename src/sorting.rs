@@ -0,0 +1,744 @@
+//! Comparison and integer sorts over any `List` implementor and over slices.
+//!
+//! The items are only rearranged by swaps, so no `Clone` bound and no copy of the items is needed.
+
+use crate::random::Random;
+use crate::CollectionError;
+use crate::List;
+use crate::INVALID_ARGUMENT_ERROR;
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+// the span of keys counting sort accepts whatever the number of items
+const MAX_COUNTING_RANGE: usize = 1 << 16;
+
+// the random access the algorithms need
+pub(in crate) trait Items<T> {
+    fn len(&self) -> usize;
+    fn item(&self, index: usize) -> &T;
+    fn swap(&mut self, a: usize, b: usize);
+}
+
+pub(in crate) struct ListItems<'a, T, L: ?Sized> {
+    list: &'a mut L,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T, L: List<T> + ?Sized> ListItems<'a, T, L> {
+    pub(in crate) fn new(list: &'a mut L) -> ListItems<'a, T, L> {
+        ListItems {
+            list,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, L: List<T> + ?Sized> Items<T> for ListItems<'a, T, L> {
+    fn len(&self) -> usize {
+        self.list.size()
+    }
+
+    fn item(&self, index: usize) -> &T {
+        match self.list.get(index) {
+            Some(item) => item,
+            None => unreachable!(),
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        match self.list.swap(a, b) {
+            Ok(()) => (),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<T> Items<T> for [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn item(&self, index: usize) -> &T {
+        &self[index]
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        <[T]>::swap(self, a, b);
+    }
+}
+
+/// Stable. O(n log n) comparisons, O(n) swaps and O(n) extra indices.
+pub fn merge_sort<T: Ord, L: List<T> + ?Sized>(list: &mut L) {
+    merge_sort_by(list, T::cmp);
+}
+
+pub fn merge_sort_by<T, L, F>(list: &mut L, mut compare: F)
+where
+    L: List<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    merge_sort_items(&mut ListItems::new(list), &mut compare);
+}
+
+pub fn merge_sort_by_key<T, L, K, F>(list: &mut L, mut key: F)
+where
+    L: List<T> + ?Sized,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    merge_sort_by(list, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Stable. O(n log n) comparisons, O(n) swaps and O(n) extra indices.
+pub fn merge_sort_slice<T: Ord>(items: &mut [T]) {
+    merge_sort_slice_by(items, T::cmp);
+}
+
+pub fn merge_sort_slice_by<T, F: FnMut(&T, &T) -> Ordering>(items: &mut [T], mut compare: F) {
+    merge_sort_items(items, &mut compare);
+}
+
+pub fn merge_sort_slice_by_key<T, K: Ord, F: FnMut(&T) -> K>(items: &mut [T], mut key: F) {
+    merge_sort_slice_by(items, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Not stable. O(n log n) expected with a random pivot, runs of equal items are
+/// partitioned out at once.
+pub fn quick_sort<T: Ord, L: List<T> + ?Sized>(list: &mut L) {
+    quick_sort_by(list, T::cmp);
+}
+
+pub fn quick_sort_by<T, L, F>(list: &mut L, mut compare: F)
+where
+    L: List<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    quick_sort_items(&mut ListItems::new(list), &mut compare);
+}
+
+pub fn quick_sort_by_key<T, L, K, F>(list: &mut L, mut key: F)
+where
+    L: List<T> + ?Sized,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    quick_sort_by(list, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Not stable. O(n log n) expected with a random pivot, runs of equal items are
+/// partitioned out at once.
+pub fn quick_sort_slice<T: Ord>(items: &mut [T]) {
+    quick_sort_slice_by(items, T::cmp);
+}
+
+pub fn quick_sort_slice_by<T, F: FnMut(&T, &T) -> Ordering>(items: &mut [T], mut compare: F) {
+    quick_sort_items(items, &mut compare);
+}
+
+pub fn quick_sort_slice_by_key<T, K: Ord, F: FnMut(&T) -> K>(items: &mut [T], mut key: F) {
+    quick_sort_slice_by(items, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Not stable. O(n log n) in the worst case without extra memory.
+pub fn heap_sort<T: Ord, L: List<T> + ?Sized>(list: &mut L) {
+    heap_sort_by(list, T::cmp);
+}
+
+pub fn heap_sort_by<T, L, F>(list: &mut L, mut compare: F)
+where
+    L: List<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    heap_sort_items(&mut ListItems::new(list), &mut compare);
+}
+
+pub fn heap_sort_by_key<T, L, K, F>(list: &mut L, mut key: F)
+where
+    L: List<T> + ?Sized,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    heap_sort_by(list, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Not stable. O(n log n) in the worst case without extra memory.
+pub fn heap_sort_slice<T: Ord>(items: &mut [T]) {
+    heap_sort_slice_by(items, T::cmp);
+}
+
+pub fn heap_sort_slice_by<T, F: FnMut(&T, &T) -> Ordering>(items: &mut [T], mut compare: F) {
+    heap_sort_items(items, &mut compare);
+}
+
+pub fn heap_sort_slice_by_key<T, K: Ord, F: FnMut(&T) -> K>(items: &mut [T], mut key: F) {
+    heap_sort_slice_by(items, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Stable. O(n + k) time and extra memory for keys spanning `k` values, failing with
+/// `INVALID_ARGUMENT_ERROR` when `k` exceeds both `n` and 2^16, a range `radix_sort` is fit for.
+pub fn counting_sort<T, L, K, F>(list: &mut L, key: F) -> Result<(), CollectionError>
+where
+    L: List<T> + ?Sized,
+    K: Into<u64>,
+    F: FnMut(&T) -> K,
+{
+    counting_sort_items(&mut ListItems::new(list), key)
+}
+
+/// Stable. O(n + k) time and extra memory for keys spanning `k` values, failing with
+/// `INVALID_ARGUMENT_ERROR` when `k` exceeds both `n` and 2^16, a range `radix_sort` is fit for.
+pub fn counting_sort_slice<T, K, F>(items: &mut [T], key: F) -> Result<(), CollectionError>
+where
+    K: Into<u64>,
+    F: FnMut(&T) -> K,
+{
+    counting_sort_items(items, key)
+}
+
+/// Stable LSD radix sort taking `digit_bits` bits of the keys at a time, which must be
+/// within `1..=16`. O((n + 2^digit_bits) * w / digit_bits) for keys of `w` significant bits.
+pub fn radix_sort<T, L, K, F>(list: &mut L, digit_bits: u32, key: F) -> Result<(), CollectionError>
+where
+    L: List<T> + ?Sized,
+    K: Into<u64>,
+    F: FnMut(&T) -> K,
+{
+    radix_sort_items(&mut ListItems::new(list), digit_bits, key)
+}
+
+/// Stable LSD radix sort taking `digit_bits` bits of the keys at a time, which must be
+/// within `1..=16`. O((n + 2^digit_bits) * w / digit_bits) for keys of `w` significant bits.
+pub fn radix_sort_slice<T, K, F>(
+    items: &mut [T],
+    digit_bits: u32,
+    key: F,
+) -> Result<(), CollectionError>
+where
+    K: Into<u64>,
+    F: FnMut(&T) -> K,
+{
+    radix_sort_items(items, digit_bits, key)
+}
+
+// sorts the indices of the items, then moves the items to their places at once
+fn merge_sort_items<T, I, F>(items: &mut I, compare: &mut F)
+where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    let mut buffer = order.clone();
+    merge_sort_indices(items, &mut order, &mut buffer, compare);
+
+    apply_order(items, &order);
+}
+
+// moves the item at `order[index]` to `index` for every index, following the cycles of
+// the permutation with O(n) swaps
+fn apply_order<T, I: Items<T> + ?Sized>(items: &mut I, order: &[usize]) {
+    let mut is_placed = vec![false; order.len()];
+    for start in 0..order.len() {
+        let mut index = start;
+        while !is_placed[index] {
+            is_placed[index] = true;
+            let source = order[index];
+            if source == start {
+                break;
+            }
+            items.swap(index, source);
+            index = source;
+        }
+    }
+}
+
+fn merge_sort_indices<T, I, F>(
+    items: &I,
+    order: &mut [usize],
+    buffer: &mut [usize],
+    compare: &mut F,
+) where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if order.len() <= 1 {
+        return;
+    }
+
+    let middle = order.len() / 2;
+    merge_sort_indices(items, &mut order[..middle], &mut buffer[..middle], compare);
+    merge_sort_indices(items, &mut order[middle..], &mut buffer[middle..], compare);
+
+    let (mut left, mut right) = (0, middle);
+    for slot in buffer.iter_mut() {
+        // the left one is taken on ties, which keeps the sort stable
+        let is_left = right == order.len()
+            || (left < middle
+                && compare(items.item(order[right]), items.item(order[left])) != Ordering::Less);
+        if is_left {
+            *slot = order[left];
+            left += 1;
+        } else {
+            *slot = order[right];
+            right += 1;
+        }
+    }
+    order.copy_from_slice(buffer);
+}
+
+fn keys<T, I, K, F>(items: &I, mut key: F) -> Vec<u64>
+where
+    I: Items<T> + ?Sized,
+    K: Into<u64>,
+    F: FnMut(&T) -> K,
+{
+    (0..items.len())
+        .map(|index| key(items.item(index)).into())
+        .collect()
+}
+
+fn counting_sort_items<T, I, K, F>(items: &mut I, key: F) -> Result<(), CollectionError>
+where
+    I: Items<T> + ?Sized,
+    K: Into<u64>,
+    F: FnMut(&T) -> K,
+{
+    let keys = keys(items, key);
+    let min = match keys.iter().min() {
+        Some(min) => *min,
+        None => return Ok(()),
+    };
+    let max = match keys.iter().max() {
+        Some(max) => *max,
+        None => unreachable!(),
+    };
+    // compared before adding one, which overflows for the full range of u64
+    if max - min >= keys.len().max(MAX_COUNTING_RANGE) as u64 {
+        return Err(INVALID_ARGUMENT_ERROR);
+    }
+
+    let mut starts = vec![0; (max - min) as usize + 1];
+    for key in keys.iter() {
+        starts[(key - min) as usize] += 1;
+    }
+    prefix_sums(&mut starts);
+
+    let mut order = vec![0; keys.len()];
+    for (index, key) in keys.iter().enumerate() {
+        let start = &mut starts[(key - min) as usize];
+        order[*start] = index;
+        *start += 1;
+    }
+
+    apply_order(items, &order);
+    Ok(())
+}
+
+// sorts the indices of the items one digit at a time, then moves the items at once
+fn radix_sort_items<T, I, K, F>(
+    items: &mut I,
+    digit_bits: u32,
+    key: F,
+) -> Result<(), CollectionError>
+where
+    I: Items<T> + ?Sized,
+    K: Into<u64>,
+    F: FnMut(&T) -> K,
+{
+    if digit_bits == 0 || digit_bits > 16 {
+        return Err(INVALID_ARGUMENT_ERROR);
+    }
+
+    let keys = keys(items, key);
+    let bits = match keys.iter().max() {
+        Some(max) => 64 - max.leading_zeros(),
+        None => return Ok(()),
+    };
+    let mask = (1 << digit_bits) - 1;
+
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    let mut buffer = vec![0; keys.len()];
+    let mut shift = 0;
+    while shift < bits {
+        let digit = |index: usize| ((keys[index] >> shift) & mask) as usize;
+
+        let mut starts = vec![0; 1 << digit_bits];
+        for index in order.iter() {
+            starts[digit(*index)] += 1;
+        }
+        prefix_sums(&mut starts);
+
+        for index in order.iter() {
+            let start = &mut starts[digit(*index)];
+            buffer[*start] = *index;
+            *start += 1;
+        }
+        std::mem::swap(&mut order, &mut buffer);
+
+        shift += digit_bits;
+    }
+
+    apply_order(items, &order);
+    Ok(())
+}
+
+// turns counts into the positions where each run starts
+fn prefix_sums(counts: &mut [usize]) {
+    let mut sum = 0;
+    for count in counts.iter_mut() {
+        let start = sum;
+        sum += *count;
+        *count = start;
+    }
+}
+
+fn quick_sort_items<T, I, F>(items: &mut I, compare: &mut F)
+where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut random = Random::new();
+    quick_sort_range(items, 0, items.len(), compare, &mut random);
+}
+
+// recurses into the smaller part only, so the depth stays O(log n)
+pub(in crate) fn quick_sort_range<T, I, F>(
+    items: &mut I,
+    mut from: usize,
+    mut to: usize,
+    compare: &mut F,
+    random: &mut Random,
+) where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    while to - from > 1 {
+        let pivot = from + random.next_below(to - from);
+        items.swap(from, pivot);
+
+        let (less, greater) = partition(items, from, to, compare);
+        if less - from < to - greater {
+            quick_sort_range(items, from, less, compare, random);
+            from = greater;
+        } else {
+            quick_sort_range(items, greater, to, compare, random);
+            to = less;
+        }
+    }
+}
+
+// partitions `from..to` around the item at `from` into smaller, equal and greater items,
+// returns where the equal ones start and end
+pub(in crate) fn partition<T, I, F>(
+    items: &mut I,
+    from: usize,
+    to: usize,
+    compare: &mut F,
+) -> (usize, usize)
+where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    // the item at `less` is always equal to the pivot
+    let mut less = from;
+    let mut index = from + 1;
+    let mut greater = to;
+
+    while index < greater {
+        match compare(items.item(index), items.item(less)) {
+            Ordering::Less => {
+                items.swap(less, index);
+                less += 1;
+                index += 1;
+            }
+            Ordering::Greater => {
+                greater -= 1;
+                items.swap(index, greater);
+            }
+            Ordering::Equal => index += 1,
+        }
+    }
+
+    (less, greater)
+}
+
+fn heap_sort_items<T, I, F>(items: &mut I, compare: &mut F)
+where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut size = items.len();
+
+    let mut index = size / 2;
+    while index > 0 {
+        index -= 1;
+        trickle_down(items, index, size, compare);
+    }
+
+    while size > 1 {
+        size -= 1;
+        items.swap(0, size);
+        trickle_down(items, 0, size, compare);
+    }
+}
+
+// restores the max-heap below `index` within the first `size` items
+fn trickle_down<T, I, F>(items: &mut I, mut index: usize, size: usize, compare: &mut F)
+where
+    I: Items<T> + ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        let left = 2 * index + 1;
+        let right = left + 1;
+
+        let mut largest = index;
+        if left < size && compare(items.item(left), items.item(largest)) == Ordering::Greater {
+            largest = left;
+        }
+        if right < size && compare(items.item(right), items.item(largest)) == Ordering::Greater {
+            largest = right;
+        }
+        if largest == index {
+            break;
+        }
+
+        items.swap(index, largest);
+        index = largest;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{counting_sort, counting_sort_slice, radix_sort, radix_sort_slice};
+    use super::{heap_sort, heap_sort_slice_by, merge_sort_by_key, merge_sort_slice};
+    use super::{quick_sort, quick_sort_by, quick_sort_slice_by_key};
+    use crate::array_deque::ArrayDeque;
+    use crate::array_stack::ArrayStack;
+    use crate::{CollectionError, Deque, List, Stack};
+    use crate::{INVALID_ARGUMENT_ERROR, OUT_OF_BOUND_ERROR};
+    use std::cmp::Reverse;
+
+    // a list relying on the default `swap`
+    struct VecList(Vec<u64>);
+
+    impl List<u64> for VecList {
+        fn size(&self) -> usize {
+            self.0.len()
+        }
+
+        fn get(&self, index: usize) -> Option<&u64> {
+            self.0.get(index)
+        }
+
+        fn set(&mut self, index: usize, item: u64) -> Result<(), CollectionError> {
+            let slot = self.0.get_mut(index).ok_or(OUT_OF_BOUND_ERROR)?;
+            *slot = item;
+            Ok(())
+        }
+
+        fn add(&mut self, index: usize, item: u64) -> Result<(), CollectionError> {
+            if index > self.0.len() {
+                return Err(OUT_OF_BOUND_ERROR);
+            }
+            self.0.insert(index, item);
+            Ok(())
+        }
+
+        fn remove(&mut self, index: usize) -> Option<u64> {
+            if index >= self.0.len() {
+                return None;
+            }
+            Some(self.0.remove(index))
+        }
+    }
+
+    fn items(count: usize) -> Vec<u64> {
+        let mut state = 42u64;
+        (0..count)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) % 50
+            })
+            .collect()
+    }
+
+    fn sorted(count: usize) -> Vec<u64> {
+        let mut items = items(count);
+        items.sort();
+        items
+    }
+
+    fn to_vec<T: Clone, L: List<T>>(list: &L) -> Vec<T> {
+        (0..list.size())
+            .map(|index| list.get(index).unwrap().clone())
+            .collect()
+    }
+
+    #[test]
+    pub fn merge() {
+        let mut slice = items(300);
+        merge_sort_slice(&mut slice);
+        assert_eq!(slice, sorted(300));
+
+        // equal keys keep the order they were added in
+        let mut stack = ArrayStack::new();
+        for (index, item) in items(300).into_iter().enumerate() {
+            stack.push((item, index));
+        }
+        merge_sort_by_key(&mut stack, |pair| pair.0);
+
+        let mut expected: Vec<(u64, usize)> = items(300).into_iter().zip(0..).collect();
+        expected.sort();
+        assert_eq!(to_vec(&stack), expected);
+    }
+
+    #[test]
+    pub fn quick() {
+        let mut deque = ArrayDeque::new();
+        for (index, item) in items(300).into_iter().enumerate() {
+            if index % 2 == 1 {
+                deque.add_first(item);
+            } else {
+                deque.add_last(item);
+            }
+        }
+        quick_sort(&mut deque);
+        assert_eq!(to_vec(&deque), sorted(300));
+
+        let mut slice = items(300);
+        quick_sort_slice_by_key(&mut slice, |item| Reverse(*item));
+        let mut expected = sorted(300);
+        expected.reverse();
+        assert_eq!(slice, expected);
+
+        let mut list = VecList(items(100));
+        quick_sort_by(&mut list, |a, b| a.cmp(b));
+        assert_eq!(list.0, sorted(100));
+
+        let mut empty: ArrayStack<u64> = ArrayStack::new();
+        quick_sort(&mut empty);
+        assert_eq!(empty.size(), 0);
+    }
+
+    #[test]
+    pub fn heap() {
+        let mut stack = ArrayStack::new();
+        for item in items(300) {
+            stack.push(item);
+        }
+        heap_sort(&mut stack);
+        assert_eq!(to_vec(&stack), sorted(300));
+
+        let mut slice = items(300);
+        heap_sort_slice_by(&mut slice, |a, b| b.cmp(a));
+        let mut expected = sorted(300);
+        expected.reverse();
+        assert_eq!(slice, expected);
+
+        let mut single = ArrayStack::new();
+        single.push(1);
+        heap_sort(&mut single);
+        assert_eq!(single.get(0), Some(&1));
+    }
+
+    #[test]
+    pub fn counting() {
+        let mut slice = items(300);
+        assert_eq!(counting_sort_slice(&mut slice, |item| *item), Ok(()));
+        assert_eq!(slice, sorted(300));
+
+        // equal keys keep the order they were added in
+        let mut deque = ArrayDeque::new();
+        for (index, item) in items(300).into_iter().enumerate() {
+            deque.add_last((item as u32 + 1000, index));
+        }
+        assert_eq!(counting_sort(&mut deque, |pair| pair.0), Ok(()));
+
+        let mut expected: Vec<(u32, usize)> = items(300)
+            .into_iter()
+            .map(|item| item as u32 + 1000)
+            .zip(0..)
+            .collect();
+        expected.sort();
+        assert_eq!(to_vec(&deque), expected);
+    }
+
+    #[test]
+    pub fn counting_range() {
+        let mut empty: [u64; 0] = [];
+        assert_eq!(counting_sort_slice(&mut empty, |item| *item), Ok(()));
+
+        let mut full = [u64::MAX, 0];
+        assert_eq!(
+            counting_sort_slice(&mut full, |item| *item),
+            Err(INVALID_ARGUMENT_ERROR)
+        );
+        assert_eq!(full, [u64::MAX, 0]);
+
+        // up to 2^16 values whatever the number of items, then up to one per item
+        let mut wide = [(1 << 16) - 1, 0u32];
+        assert_eq!(counting_sort_slice(&mut wide, |item| *item), Ok(()));
+        assert_eq!(wide, [0, (1 << 16) - 1]);
+        let mut wide = [1 << 16, 0u32];
+        assert_eq!(
+            counting_sort_slice(&mut wide, |item| *item),
+            Err(INVALID_ARGUMENT_ERROR)
+        );
+
+        let mut many: Vec<u32> = (0..100_000).rev().collect();
+        assert_eq!(counting_sort_slice(&mut many, |item| *item), Ok(()));
+        assert!(many.windows(2).all(|pair| pair[0] < pair[1]));
+        many.push(200_000);
+        assert_eq!(
+            counting_sort_slice(&mut many, |item| *item),
+            Err(INVALID_ARGUMENT_ERROR)
+        );
+    }
+
+    #[test]
+    pub fn radix() {
+        let timestamps: Vec<u64> = items(300)
+            .into_iter()
+            .map(|item| item.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect();
+        let mut expected = timestamps.clone();
+        expected.sort();
+
+        for digit_bits in [1, 8, 11, 16].iter() {
+            let mut stack = ArrayStack::new();
+            for (index, timestamp) in timestamps.iter().enumerate() {
+                stack.push((*timestamp, index));
+            }
+            assert_eq!(radix_sort(&mut stack, *digit_bits, |pair| pair.0), Ok(()));
+
+            let sorted: Vec<u64> = to_vec(&stack).into_iter().map(|pair| pair.0).collect();
+            assert_eq!(sorted, expected);
+            let stable = (1..stack.size()).all(|index| {
+                let (a, b) = (stack.get(index - 1).unwrap(), stack.get(index).unwrap());
+                a.0 < b.0 || a.1 < b.1
+            });
+            assert!(stable);
+        }
+
+        let mut slice: Vec<u32> = items(300).into_iter().map(|item| item as u32).collect();
+        assert_eq!(radix_sort_slice(&mut slice, 4, |item| *item), Ok(()));
+        assert_eq!(
+            slice,
+            sorted(300)
+                .into_iter()
+                .map(|item| item as u32)
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            radix_sort_slice(&mut slice, 0, |item| *item),
+            Err(INVALID_ARGUMENT_ERROR)
+        );
+        assert_eq!(
+            radix_sort_slice(&mut slice, 17, |item| *item),
+            Err(INVALID_ARGUMENT_ERROR)
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use crate::backend::{Array, Entry};
+use crate::CollectionError;
+use crate::OUT_OF_BOUND_ERROR;
+
+const DEFAULT_BACKEND_SIZE: usize = 2;
+const SIZE_UP_MULTIPLIER_NUMBER: usize = 2;
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    parent: usize,
+    rank: u32,
+    // the number of elements under a root
+    size: usize,
+}
+
+/// Disjoint sets over the elements `0..size`, in O(α(n)) amortized per operation with union by
+/// rank and path compression.
+#[derive(Debug)]
+pub struct UnionFind {
+    backend: Array<Node>,
+    size: usize,
+    set_count: usize,
+}
+
+impl UnionFind {
+    pub fn new() -> UnionFind {
+        UnionFind {
+            backend: Array::new(DEFAULT_BACKEND_SIZE),
+            size: 0,
+            set_count: 0,
+        }
+    }
+
+    /// The `size` singletons `{0}` to `{size - 1}`.
+    pub fn with_size(size: usize) -> UnionFind {
+        let mut union_find = UnionFind {
+            backend: Array::new(std::cmp::max(size, DEFAULT_BACKEND_SIZE)),
+            size: 0,
+            set_count: 0,
+        };
+        for _ in 0..size {
+            union_find.make_set();
+        }
+        union_find
+    }
+
+    /// The number of elements.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    /// Adds the singleton of a new element and returns it.
+    pub fn make_set(&mut self) -> usize {
+        if self.size == self.backend.len() {
+            self.backend
+                .resize(self.backend.len() * SIZE_UP_MULTIPLIER_NUMBER, 0, self.size);
+        }
+
+        let x = self.size;
+        self.set(
+            x,
+            Node {
+                parent: x,
+                rank: 0,
+                size: 1,
+            },
+        );
+        self.size += 1;
+        self.set_count += 1;
+        x
+    }
+
+    /// The representative of the set of `x`, `None` when `x` is out of bound.
+    pub fn find(&mut self, x: usize) -> Option<usize> {
+        if x >= self.size {
+            return None;
+        }
+
+        let mut root = x;
+        while self.node(root).parent != root {
+            root = self.node(root).parent;
+        }
+
+        // points the whole path at the root
+        let mut current = x;
+        while current != root {
+            let parent = self.node(current).parent;
+            self.set_parent(current, root);
+            current = parent;
+        }
+
+        Some(root)
+    }
+
+    /// Merges the sets of `x` and `y`, false when they are the same set.
+    pub fn union(&mut self, x: usize, y: usize) -> Result<bool, CollectionError> {
+        let (x, y) = match (self.find(x), self.find(y)) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return Err(OUT_OF_BOUND_ERROR),
+        };
+        if x == y {
+            return Ok(false);
+        }
+
+        let (mut root, mut child) = (self.node(x), self.node(y));
+        let (mut root_index, mut child_index) = (x, y);
+        if root.rank < child.rank {
+            std::mem::swap(&mut root, &mut child);
+            std::mem::swap(&mut root_index, &mut child_index);
+        }
+
+        if root.rank == child.rank {
+            root.rank += 1;
+        }
+        root.size += child.size;
+        child.parent = root_index;
+        self.set(root_index, root);
+        self.set(child_index, child);
+        self.set_count -= 1;
+
+        Ok(true)
+    }
+
+    /// False when `x` or `y` is out of bound.
+    pub fn same_set(&mut self, x: usize, y: usize) -> bool {
+        match (self.find(x), self.find(y)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    }
+
+    /// The number of elements in the set of `x`.
+    pub fn set_size(&mut self, x: usize) -> Option<usize> {
+        let root = self.find(x)?;
+        Some(self.node(root).size)
+    }
+
+    /// Iterates the sets, each in ascending order, ordered by their least element.
+    pub fn sets(&mut self) -> Sets {
+        // the position of each root in `sets`
+        let mut positions: Vec<Option<usize>> = vec![None; self.size];
+        let mut sets: Vec<Vec<usize>> = vec![];
+        for x in 0..self.size {
+            let root = self.find(x).unwrap();
+            match positions[root] {
+                Some(position) => sets[position].push(x),
+                None => {
+                    positions[root] = Some(sets.len());
+                    sets.push(vec![x]);
+                }
+            }
+        }
+
+        Sets {
+            sets: sets.into_iter(),
+        }
+    }
+
+    fn node(&self, index: usize) -> Node {
+        match self.backend.get(index) {
+            Some(Entry::Item(node)) => *node,
+            _ => unreachable!(),
+        }
+    }
+
+    fn set(&mut self, index: usize, node: Node) {
+        match self.backend.set(index, node) {
+            Ok(()) => (),
+            _ => unreachable!(),
+        }
+    }
+
+    fn set_parent(&mut self, index: usize, parent: usize) {
+        let mut node = self.node(index);
+        node.parent = parent;
+        self.set(index, node);
+    }
+}
+
+impl Default for UnionFind {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub struct Sets {
+    sets: std::vec::IntoIter<Vec<usize>>,
+}
+
+impl Iterator for Sets {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        self.sets.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnionFind;
+    use crate::OUT_OF_BOUND_ERROR;
+
+    #[test]
+    pub fn union_find() {
+        let mut union_find = UnionFind::with_size(6);
+        assert_eq!(union_find.size(), 6);
+        assert_eq!(union_find.set_count(), 6);
+
+        assert_eq!(union_find.union(0, 1), Ok(true));
+        assert_eq!(union_find.union(2, 3), Ok(true));
+        assert_eq!(union_find.union(1, 3), Ok(true));
+        assert_eq!(union_find.union(0, 2), Ok(false));
+        assert_eq!(union_find.union(0, 6), Err(OUT_OF_BOUND_ERROR));
+        assert_eq!(union_find.set_count(), 3);
+
+        assert!(union_find.same_set(0, 3));
+        assert!(!union_find.same_set(0, 4));
+        assert!(!union_find.same_set(0, 6));
+        assert_eq!(union_find.find(3), union_find.find(0));
+        assert_eq!(union_find.find(6), None);
+
+        assert_eq!(union_find.set_size(2), Some(4));
+        assert_eq!(union_find.set_size(5), Some(1));
+        assert_eq!(union_find.set_size(6), None);
+    }
+
+    #[test]
+    pub fn make_set_and_sets() {
+        let mut union_find = UnionFind::new();
+        assert_eq!(union_find.sets().count(), 0);
+
+        for x in 0..10 {
+            assert_eq!(union_find.make_set(), x);
+        }
+        for x in 0..10 {
+            union_find.union(x, x % 3).unwrap();
+        }
+
+        let sets: Vec<Vec<usize>> = union_find.sets().collect();
+        assert_eq!(sets, vec![vec![0, 3, 6, 9], vec![1, 4, 7], vec![2, 5, 8]]);
+        assert_eq!(union_find.set_count(), 3);
+    }
+}
@@ -0,0 +1,551 @@
+use crate::binary_trie::locate;
+use crate::CollectionError;
+use crate::SSet;
+use crate::INVALID_ARGUMENT_ERROR;
+use std::borrow::Borrow;
+
+const DEFAULT_BITS: u32 = 32;
+// the bits of the universe of a leaf
+const LEAF_BITS: u32 = 6;
+
+#[derive(Debug)]
+enum Node {
+    // the keys of the set bits, in ascending order
+    Leaf { bitmap: u64, keys: Vec<u32> },
+    Branch(Box<Branch>),
+}
+
+// of a universe of `bits` bits, split into `2^(bits - bits / 2)` clusters of `bits / 2` bits
+#[derive(Debug)]
+struct Branch {
+    // the least key, kept out of the clusters
+    min: u32,
+    // the greatest key, also in its cluster unless it is the least
+    max: u32,
+    // the indices of the non-empty clusters
+    summary: Option<Node>,
+    // allocated with the first cluster, an empty cluster being `None`
+    clusters: Vec<Option<Node>>,
+}
+
+// the bit of `key` in a leaf of `bits` bits, keys being absolute
+fn position(key: u32, bits: u32) -> usize {
+    (u64::from(key) & ((1 << bits) - 1)) as usize
+}
+
+// the cluster of `key` in a branch of `bits` bits
+fn cluster(key: u32, bits: u32) -> usize {
+    position(key, bits) >> (bits / 2)
+}
+
+impl Node {
+    fn new(key: u32, bits: u32) -> Node {
+        if bits <= LEAF_BITS {
+            Node::Leaf {
+                bitmap: 1 << position(key, bits),
+                keys: vec![key],
+            }
+        } else {
+            Node::Branch(Box::new(Branch {
+                min: key,
+                max: key,
+                summary: None,
+                clusters: vec![],
+            }))
+        }
+    }
+
+    fn min(&self) -> &u32 {
+        match self {
+            Node::Leaf { keys, .. } => &keys[0],
+            Node::Branch(branch) => &branch.min,
+        }
+    }
+
+    fn max(&self) -> &u32 {
+        match self {
+            Node::Leaf { keys, .. } => &keys[keys.len() - 1],
+            Node::Branch(branch) => &branch.max,
+        }
+    }
+
+    fn find(&self, key: u32, bits: u32) -> Option<&u32> {
+        match self {
+            Node::Leaf { bitmap, keys } => {
+                let position = position(key, bits);
+                if bitmap >> position & 1 == 0 {
+                    return None;
+                }
+                Some(&keys[rank(*bitmap, position)])
+            }
+            Node::Branch(branch) => {
+                if key == branch.min {
+                    return Some(&branch.min);
+                }
+                if key == branch.max {
+                    return Some(&branch.max);
+                }
+                branch
+                    .clusters
+                    .get(cluster(key, bits))?
+                    .as_ref()?
+                    .find(key, bits / 2)
+            }
+        }
+    }
+
+    // the least key greater than `key`
+    fn successor(&self, key: u32, bits: u32) -> Option<&u32> {
+        match self {
+            Node::Leaf { bitmap, keys } => {
+                let position = position(key, bits);
+                let after = if position == 63 {
+                    0
+                } else {
+                    bitmap & (u64::MAX << (position + 1))
+                };
+                if after == 0 {
+                    return None;
+                }
+                Some(&keys[rank(*bitmap, after.trailing_zeros() as usize)])
+            }
+            Node::Branch(branch) => {
+                if key < branch.min {
+                    return Some(&branch.min);
+                }
+                if key >= branch.max {
+                    return None;
+                }
+
+                let index = cluster(key, bits);
+                if let Some(Some(cluster)) = branch.clusters.get(index) {
+                    if key < *cluster.max() {
+                        return cluster.successor(key, bits / 2);
+                    }
+                }
+                let summary = branch.summary.as_ref()?;
+                let next = *summary.successor(index as u32, bits - bits / 2)?;
+                branch.clusters[next as usize].as_ref().map(Node::min)
+            }
+        }
+    }
+
+    // the greatest key less than `key`
+    fn predecessor(&self, key: u32, bits: u32) -> Option<&u32> {
+        match self {
+            Node::Leaf { bitmap, keys } => {
+                let before = bitmap & ((1 << position(key, bits)) - 1);
+                if before == 0 {
+                    return None;
+                }
+                Some(&keys[rank(*bitmap, 63 - before.leading_zeros() as usize)])
+            }
+            Node::Branch(branch) => {
+                if key > branch.max {
+                    return Some(&branch.max);
+                }
+                if key <= branch.min {
+                    return None;
+                }
+
+                let index = cluster(key, bits);
+                if let Some(Some(cluster)) = branch.clusters.get(index) {
+                    if key > *cluster.min() {
+                        return cluster.predecessor(key, bits / 2);
+                    }
+                }
+                let previous = branch
+                    .summary
+                    .as_ref()
+                    .and_then(|summary| summary.predecessor(index as u32, bits - bits / 2));
+                match previous {
+                    Some(&previous) => branch.clusters[previous as usize].as_ref().map(Node::max),
+                    None => Some(&branch.min),
+                }
+            }
+        }
+    }
+}
+
+// the number of set bits below `position`
+fn rank(bitmap: u64, position: usize) -> usize {
+    (bitmap & ((1 << position) - 1)).count_ones() as usize
+}
+
+fn insert(node: &mut Option<Node>, key: u32, bits: u32) -> bool {
+    let node = match node {
+        Some(node) => node,
+        None => {
+            *node = Some(Node::new(key, bits));
+            return true;
+        }
+    };
+
+    match node {
+        Node::Leaf { bitmap, keys } => {
+            let position = position(key, bits);
+            if *bitmap >> position & 1 == 1 {
+                return false;
+            }
+            keys.insert(rank(*bitmap, position), key);
+            *bitmap |= 1 << position;
+            true
+        }
+        Node::Branch(branch) => {
+            if key == branch.min || key == branch.max {
+                return false;
+            }
+
+            // the least key stays out of the clusters
+            let mut key = key;
+            if key > branch.max {
+                branch.max = key;
+            }
+            if key < branch.min {
+                std::mem::swap(&mut key, &mut branch.min);
+            }
+
+            if branch.clusters.is_empty() {
+                branch.clusters = (0..1 << (bits - bits / 2)).map(|_| None).collect();
+            }
+            let index = cluster(key, bits);
+            if branch.clusters[index].is_none() {
+                insert(&mut branch.summary, index as u32, bits - bits / 2);
+            }
+            insert(&mut branch.clusters[index], key, bits / 2)
+        }
+    }
+}
+
+fn remove(node: &mut Option<Node>, key: u32, bits: u32) -> bool {
+    let is_empty = match node {
+        None => return false,
+        Some(Node::Leaf { bitmap, keys }) => {
+            let position = position(key, bits);
+            if *bitmap >> position & 1 == 0 {
+                return false;
+            }
+            keys.remove(rank(*bitmap, position));
+            *bitmap &= !(1 << position);
+            *bitmap == 0
+        }
+        Some(Node::Branch(branch)) => {
+            let mut key = key;
+            if key == branch.min {
+                let first = match &branch.summary {
+                    Some(summary) => *summary.min() as usize,
+                    None => {
+                        *node = None;
+                        return true;
+                    }
+                };
+                // the least clustered key replaces the least key
+                key = match &branch.clusters[first] {
+                    Some(cluster) => *cluster.min(),
+                    None => unreachable!(),
+                };
+                branch.min = key;
+            }
+
+            let index = cluster(key, bits);
+            if index >= branch.clusters.len() || !remove(&mut branch.clusters[index], key, bits / 2)
+            {
+                return false;
+            }
+            if branch.clusters[index].is_none() {
+                remove(&mut branch.summary, index as u32, bits - bits / 2);
+            }
+
+            match &branch.summary {
+                None => {
+                    branch.clusters = vec![];
+                    branch.max = branch.min;
+                }
+                Some(summary) if key == branch.max => {
+                    branch.max = match &branch.clusters[*summary.max() as usize] {
+                        Some(cluster) => *cluster.max(),
+                        None => unreachable!(),
+                    };
+                }
+                Some(_) => (),
+            }
+            false
+        }
+    };
+
+    if is_empty {
+        *node = None;
+    }
+    true
+}
+
+/// An ordered set of the keys `0..2^bits`. Every operation is O(log log U) for the universe size
+/// U = 2^bits, clusters being allocated as they get their first key, with bitmaps of 64 keys at the
+/// bottom.
+///
+/// Items outside the universe are never added. `contains`, `successor_key`, `predecessor_key`
+/// and `remove_key` take the key itself, the `SSet` queries first locate their `Q` among the keys
+/// in 32 comparisons.
+#[derive(Debug)]
+pub struct VanEmdeBoasTree {
+    root: Option<Node>,
+    bits: u32,
+    size: usize,
+}
+
+impl VanEmdeBoasTree {
+    /// The universe of every `u32`.
+    pub fn new() -> VanEmdeBoasTree {
+        VanEmdeBoasTree {
+            root: None,
+            bits: DEFAULT_BITS,
+            size: 0,
+        }
+    }
+
+    /// The universe `0..2^bits`, `bits` within `1..=32`.
+    pub fn with_bits(bits: u32) -> Result<VanEmdeBoasTree, CollectionError> {
+        if !(1..=DEFAULT_BITS).contains(&bits) {
+            return Err(INVALID_ARGUMENT_ERROR);
+        }
+        Ok(VanEmdeBoasTree {
+            root: None,
+            bits,
+            size: 0,
+        })
+    }
+
+    /// The number of keys of the universe.
+    pub fn universe(&self) -> u64 {
+        1 << self.bits
+    }
+
+    pub fn contains(&self, item: u32) -> bool {
+        self.lookup(item).is_some()
+    }
+
+    /// The least item greater than `item`.
+    pub fn successor_key(&self, item: u32) -> Option<&u32> {
+        if u64::from(item) >= self.universe() {
+            return None;
+        }
+        self.root.as_ref()?.successor(item, self.bits)
+    }
+
+    /// The greatest item less than `item`.
+    pub fn predecessor_key(&self, item: u32) -> Option<&u32> {
+        let root = self.root.as_ref()?;
+        if u64::from(item) >= self.universe() {
+            return Some(root.max());
+        }
+        root.predecessor(item, self.bits)
+    }
+
+    pub fn remove_key(&mut self, item: u32) -> bool {
+        if u64::from(item) >= self.universe() || !remove(&mut self.root, item, self.bits) {
+            return false;
+        }
+        self.size -= 1;
+        true
+    }
+
+    fn lookup(&self, item: u32) -> Option<&u32> {
+        if u64::from(item) >= self.universe() {
+            return None;
+        }
+        self.root.as_ref()?.find(item, self.bits)
+    }
+}
+
+impl Default for VanEmdeBoasTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SSet<u32> for VanEmdeBoasTree {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, item: u32) -> bool {
+        if u64::from(item) >= self.universe() || !insert(&mut self.root, item, self.bits) {
+            return false;
+        }
+        self.size += 1;
+        true
+    }
+
+    fn take<Q>(&mut self, item: &Q) -> Option<u32>
+    where
+        u32: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<u32, Q>(item)? {
+            (key, true) if self.remove_key(key as u32) => Some(key as u32),
+            _ => None,
+        }
+    }
+
+    fn find<Q>(&self, item: &Q) -> Option<&u32>
+    where
+        u32: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let key = locate::<u32, Q>(item)?.0 as u32;
+        self.lookup(key).or_else(|| self.successor_key(key))
+    }
+
+    fn successor<Q>(&self, item: &Q) -> Option<&u32>
+    where
+        u32: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<u32, Q>(item)? {
+            (key, true) => self.successor_key(key as u32),
+            (key, false) => self
+                .lookup(key as u32)
+                .or_else(|| self.successor_key(key as u32)),
+        }
+    }
+
+    fn predecessor<Q>(&self, item: &Q) -> Option<&u32>
+    where
+        u32: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<u32, Q>(item) {
+            Some((key, _)) => self.predecessor_key(key as u32),
+            None => self.last(),
+        }
+    }
+
+    fn first(&self) -> Option<&u32> {
+        self.root.as_ref().map(Node::min)
+    }
+
+    fn last(&self) -> Option<&u32> {
+        self.root.as_ref().map(Node::max)
+    }
+
+    fn pop_first(&mut self) -> Option<u32> {
+        let first = *self.first()?;
+        self.remove_key(first);
+        Some(first)
+    }
+
+    fn pop_last(&mut self) -> Option<u32> {
+        let last = *self.last()?;
+        self.remove_key(last);
+        Some(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Branch, Node, VanEmdeBoasTree};
+    use crate::SSet;
+    use crate::INVALID_ARGUMENT_ERROR;
+
+    fn root(tree: &VanEmdeBoasTree) -> &Branch {
+        match &tree.root {
+            Some(Node::Branch(branch)) => branch,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    pub fn sset() {
+        crate::tests::sset(VanEmdeBoasTree::new());
+    }
+
+    #[test]
+    pub fn universe() {
+        assert_eq!(
+            VanEmdeBoasTree::with_bits(0).err(),
+            Some(INVALID_ARGUMENT_ERROR)
+        );
+        assert_eq!(
+            VanEmdeBoasTree::with_bits(33).err(),
+            Some(INVALID_ARGUMENT_ERROR)
+        );
+
+        // the least and the greatest key of every universe, through leaves and branches
+        for bits in 1..=32 {
+            let mut tree = VanEmdeBoasTree::with_bits(bits).unwrap();
+            let max = (tree.universe() - 1) as u32;
+            assert!(tree.add(max));
+            assert!(tree.add(0));
+            if bits < 32 {
+                assert!(!tree.add(max + 1));
+                assert_eq!(tree.successor_key(max + 1), None);
+                assert_eq!(tree.predecessor_key(u32::MAX), Some(&max));
+            }
+            assert!(tree.contains(max));
+            assert_eq!(tree.successor_key(0), Some(&max));
+            assert_eq!(tree.predecessor_key(max), Some(&0));
+            assert_eq!(tree.successor_key(max), None);
+
+            assert!(tree.remove_key(0));
+            assert_eq!(tree.first(), Some(&max));
+            assert!(tree.remove_key(max));
+            assert!(tree.root.is_none());
+        }
+    }
+
+    #[test]
+    pub fn clusters() {
+        // 256 clusters of 8 bits, the least key kept out of them
+        let mut tree = VanEmdeBoasTree::with_bits(16).unwrap();
+        assert!(tree.add(0));
+        assert!(root(&tree).clusters.is_empty());
+        for item in [256, 257, 300, 1000].iter() {
+            assert!(tree.add(*item));
+        }
+        assert_eq!(root(&tree).clusters.len(), 256);
+        assert!(root(&tree).clusters[0].is_none());
+        assert!(root(&tree).clusters[1].is_some());
+        assert!(root(&tree).clusters[3].is_some());
+        assert_eq!(root(&tree).summary.as_ref().unwrap().min(), &1);
+
+        // an emptied cluster leaves the summary
+        for item in [257, 256, 300].iter() {
+            assert!(tree.remove_key(*item));
+        }
+        assert!(root(&tree).clusters[1].is_none());
+        assert_eq!(root(&tree).summary.as_ref().unwrap().min(), &3);
+        assert_eq!(tree.successor_key(0), Some(&1000));
+        assert_eq!(tree.predecessor_key(1000), Some(&0));
+
+        // the least clustered key replaces a removed least key
+        assert!(tree.remove_key(0));
+        assert_eq!(root(&tree).min, 1000);
+        assert!(root(&tree).summary.is_none());
+        assert!(root(&tree).clusters.is_empty());
+        assert_eq!(tree.first(), Some(&1000));
+        assert_eq!(tree.last(), Some(&1000));
+
+        assert!(tree.remove_key(1000));
+        assert!(tree.root.is_none());
+        assert_eq!(tree.size(), 0);
+    }
+
+    #[test]
+    pub fn borrowed() {
+        let mut tree = VanEmdeBoasTree::with_bits(10).unwrap();
+        for item in (0..1 << 10).filter(|x| x % 7 == 0) {
+            tree.add(item);
+        }
+        for x in (0..1 << 11).chain(u32::MAX - 2..=u32::MAX) {
+            assert_eq!(tree.successor(&x), tree.successor_key(x));
+            assert_eq!(tree.predecessor(&x), tree.predecessor_key(x));
+        }
+        assert!(!tree.remove_key(1 << 10));
+        assert!(!tree.remove_key(8));
+        assert!(tree.remove_key(14));
+        assert_eq!(tree.take(&14), None);
+        assert!(tree.remove(&21));
+        assert!(!tree.remove(&21));
+        assert_eq!(tree.size(), 145);
+    }
+}
@@ -0,0 +1,461 @@
+use crate::binary_trie::{locate, Word};
+use crate::chained_hash_table::ChainedHashTable;
+use crate::{SSet, USet};
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+
+const ROOT: usize = 0;
+
+// a node of a level, known by the bits of its path
+#[derive(Debug)]
+struct Prefix {
+    prefix: u64,
+    node: usize,
+}
+
+impl PartialEq for Prefix {
+    fn eq(&self, other: &Self) -> bool {
+        self.prefix == other.prefix
+    }
+}
+
+impl Eq for Prefix {}
+
+impl Hash for Prefix {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.prefix.hash(state);
+    }
+}
+
+impl Borrow<u64> for Prefix {
+    fn borrow(&self) -> &u64 {
+        &self.prefix
+    }
+}
+
+#[derive(Debug)]
+struct Node<T> {
+    parent: Option<usize>,
+    children: [Option<usize>; 2],
+    // on a node with one child, the least leaf of its right subtree or the greatest of its left
+    jump: Option<usize>,
+    // the leaf list, on leaves only
+    prev: Option<usize>,
+    next: Option<usize>,
+    item: Option<T>,
+}
+
+impl<T> Node<T> {
+    fn new(parent: Option<usize>) -> Node<T> {
+        Node {
+            parent,
+            children: [None, None],
+            jump: None,
+            prev: None,
+            next: None,
+            item: None,
+        }
+    }
+}
+
+/// A binary trie whose nodes are also kept in one hash table per level, so the deepest node on
+/// the path of a key is found by binary search over the levels: `find_key`, `successor_key` and
+/// `predecessor_key` take O(log W) expected hash table lookups, `add` and `take` O(W) expected.
+///
+/// The `SSet` queries take any `Q` the items borrow as, whose bits are unknown, so they first
+/// locate it among the words in O(W) comparisons.
+#[derive(Debug)]
+pub struct XFastTrie<T> {
+    nodes: Vec<Node<T>>,
+    // the unused slots of `nodes`
+    free: Vec<usize>,
+    // the nodes at depth `i` in `levels[i]`
+    levels: Vec<ChainedHashTable<Prefix>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    size: usize,
+}
+
+impl<T: Word> XFastTrie<T> {
+    pub fn new() -> XFastTrie<T> {
+        let mut levels: Vec<ChainedHashTable<Prefix>> =
+            (0..=T::W).map(|_| ChainedHashTable::new()).collect();
+        levels[0].add(Prefix {
+            prefix: 0,
+            node: ROOT,
+        });
+
+        XFastTrie {
+            nodes: vec![Node::new(None)],
+            free: vec![],
+            levels,
+            head: None,
+            tail: None,
+            size: 0,
+        }
+    }
+
+    fn item(&self, leaf: usize) -> &T {
+        match &self.nodes[leaf].item {
+            Some(item) => item,
+            _ => unreachable!(),
+        }
+    }
+
+    fn key(&self, leaf: usize) -> u64 {
+        self.item(leaf).to_u64()
+    }
+
+    fn prefix(key: u64, depth: u32) -> u64 {
+        if depth == 0 {
+            0
+        } else {
+            key >> (T::W - depth)
+        }
+    }
+
+    fn bit(key: u64, depth: u32) -> usize {
+        ((key >> (T::W - 1 - depth)) & 1) as usize
+    }
+
+    fn leaf(&self, key: u64) -> Option<usize> {
+        self.levels[T::W as usize]
+            .find(&key)
+            .map(|prefix| prefix.node)
+    }
+
+    // the least leaf with a key greater than or equal to `key`
+    fn search(&self, key: u64) -> Option<usize> {
+        if self.size == 0 {
+            return None;
+        }
+
+        // the node at `low` is on the path of `key`, none at `high` is
+        let (mut low, mut high) = (0, T::W + 1);
+        let mut node = ROOT;
+        while high - low > 1 {
+            let middle = (low + high) / 2;
+            match self.levels[middle as usize].find(&Self::prefix(key, middle)) {
+                Some(prefix) => {
+                    low = middle;
+                    node = prefix.node;
+                }
+                None => high = middle,
+            }
+        }
+        if low == T::W {
+            return Some(node);
+        }
+
+        // the path of `key` leaves the trie at a node with one child
+        let jump = self.nodes[node].jump.unwrap();
+        if Self::bit(key, low) == 0 {
+            Some(jump)
+        } else {
+            self.nodes[jump].next
+        }
+    }
+
+    /// The least item greater than or equal to `item`.
+    pub fn find_key(&self, item: T) -> Option<&T> {
+        let leaf = self.search(item.to_u64())?;
+        Some(self.item(leaf))
+    }
+
+    /// The least item greater than `item`.
+    pub fn successor_key(&self, item: T) -> Option<&T> {
+        match item.to_u64() {
+            key if key == T::MAX => None,
+            key => {
+                let leaf = self.search(key + 1)?;
+                Some(self.item(leaf))
+            }
+        }
+    }
+
+    /// The greatest item less than `item`.
+    pub fn predecessor_key(&self, item: T) -> Option<&T> {
+        let leaf = match self.search(item.to_u64()) {
+            Some(next) => self.nodes[next].prev?,
+            None => self.tail?,
+        };
+        Some(self.item(leaf))
+    }
+
+    fn allocate(&mut self, parent: usize) -> usize {
+        match self.free.pop() {
+            Some(node) => {
+                self.nodes[node] = Node::new(Some(parent));
+                node
+            }
+            None => {
+                self.nodes.push(Node::new(Some(parent)));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) -> T {
+        let (prev, next) = (self.nodes[leaf].prev, self.nodes[leaf].next);
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+
+        let item = match self.nodes[leaf].item.take() {
+            Some(item) => item,
+            _ => unreachable!(),
+        };
+        let key = item.to_u64();
+
+        // drops the nodes left without a leaf
+        let mut node = leaf;
+        let mut depth = T::W;
+        let mut parent = self.nodes[node].parent.unwrap();
+        loop {
+            self.nodes[parent].children[Self::bit(key, depth - 1)] = None;
+            self.levels[depth as usize].remove(&Self::prefix(key, depth));
+            self.free.push(node);
+
+            if parent == ROOT || self.nodes[parent].children != [None, None] {
+                break;
+            }
+            node = parent;
+            depth -= 1;
+            parent = self.nodes[node].parent.unwrap();
+        }
+
+        // the jump pointers to the leaf move to its neighbour in the same subtree
+        let mut current = Some(parent);
+        while let Some(node) = current {
+            let jump = self.nodes[node].jump;
+            self.nodes[node].jump = match self.nodes[node].children {
+                [None, Some(_)] if jump.is_none() || jump == Some(leaf) => next,
+                [Some(_), None] if jump.is_none() || jump == Some(leaf) => prev,
+                [Some(_), Some(_)] | [None, None] => None,
+                _ => jump,
+            };
+            current = self.nodes[node].parent;
+        }
+
+        self.size -= 1;
+        item
+    }
+}
+
+impl<T: Word> Default for XFastTrie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Word> SSet<T> for XFastTrie<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, item: T) -> bool {
+        let key = item.to_u64();
+        if self.leaf(key).is_some() {
+            return false;
+        }
+
+        let next = self.search(key);
+        let prev = match next {
+            Some(next) => self.nodes[next].prev,
+            None => self.tail,
+        };
+
+        let mut node = ROOT;
+        for depth in 0..T::W {
+            node = match self.nodes[node].children[Self::bit(key, depth)] {
+                Some(child) => child,
+                None => {
+                    let child = self.allocate(node);
+                    self.nodes[node].children[Self::bit(key, depth)] = Some(child);
+                    self.levels[depth as usize + 1].add(Prefix {
+                        prefix: Self::prefix(key, depth + 1),
+                        node: child,
+                    });
+                    child
+                }
+            };
+        }
+
+        let leaf = node;
+        self.nodes[leaf].prev = prev;
+        self.nodes[leaf].next = next;
+        self.nodes[leaf].item = Some(item);
+        match prev {
+            Some(prev) => self.nodes[prev].next = Some(leaf),
+            None => self.head = Some(leaf),
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = Some(leaf),
+            None => self.tail = Some(leaf),
+        }
+
+        let mut current = self.nodes[leaf].parent;
+        while let Some(node) = current {
+            let jump = self.nodes[node].jump;
+            self.nodes[node].jump = match self.nodes[node].children {
+                [None, Some(_)] if jump.is_none_or(|jump| self.key(jump) > key) => Some(leaf),
+                [Some(_), None] if jump.is_none_or(|jump| self.key(jump) < key) => Some(leaf),
+                [Some(_), Some(_)] => None,
+                _ => jump,
+            };
+            current = self.nodes[node].parent;
+        }
+
+        self.size += 1;
+        true
+    }
+
+    /// O(W) to locate `item`.
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<T, Q>(item) {
+            Some((key, true)) => {
+                let leaf = self.leaf(key)?;
+                Some(self.remove_leaf(leaf))
+            }
+            _ => None,
+        }
+    }
+
+    /// O(W) to locate `item`, against O(log W) for `find_key`.
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (key, _) = locate::<T, Q>(item)?;
+        self.find_key(T::from_u64(key))
+    }
+
+    /// O(W) to locate `item`, against O(log W) for `successor_key`.
+    fn successor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<T, Q>(item)? {
+            (key, true) => self.successor_key(T::from_u64(key)),
+            (key, false) => self.find_key(T::from_u64(key)),
+        }
+    }
+
+    /// O(W) to locate `item`, against O(log W) for `predecessor_key`.
+    fn predecessor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<T, Q>(item) {
+            Some((key, _)) => self.predecessor_key(T::from_u64(key)),
+            None => self.last(),
+        }
+    }
+
+    fn first(&self) -> Option<&T> {
+        Some(self.item(self.head?))
+    }
+
+    fn last(&self) -> Option<&T> {
+        Some(self.item(self.tail?))
+    }
+
+    fn pop_first(&mut self) -> Option<T> {
+        let head = self.head?;
+        Some(self.remove_leaf(head))
+    }
+
+    fn pop_last(&mut self) -> Option<T> {
+        let tail = self.tail?;
+        Some(self.remove_leaf(tail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XFastTrie;
+    use crate::{SSet, USet};
+
+    #[test]
+    pub fn sset() {
+        crate::tests::sset(XFastTrie::new());
+    }
+
+    #[test]
+    pub fn full_width() {
+        // the prefixes of the root and of the leaves shift by 64 and by 0 bits
+        let mut trie = XFastTrie::new();
+        for item in [u64::MAX, 0, 1 << 63, 42].iter() {
+            trie.add(*item);
+        }
+        assert_eq!(trie.first(), Some(&0));
+        assert_eq!(trie.last(), Some(&u64::MAX));
+        assert_eq!(trie.find_key(43), Some(&(1 << 63)));
+        assert_eq!(trie.successor_key(u64::MAX), None);
+        assert_eq!(trie.predecessor_key(1 << 63), Some(&42));
+
+        assert_eq!(trie.pop_first(), Some(0));
+        assert_eq!(trie.pop_last(), Some(u64::MAX));
+        assert_eq!(trie.pop_last(), Some(1 << 63));
+        assert_eq!(trie.pop_first(), Some(42));
+        assert_eq!(trie.pop_first(), None);
+    }
+
+    #[test]
+    pub fn levels() {
+        let sizes = |trie: &XFastTrie<u8>| -> Vec<usize> {
+            trie.levels.iter().map(|level| level.size()).collect()
+        };
+
+        // one prefix per level and distinct path
+        let mut trie = XFastTrie::new();
+        for item in [0b0000_0000u8, 0b0000_0011, 0b1000_0000].iter() {
+            trie.add(*item);
+        }
+        assert_eq!(sizes(&trie), vec![1, 2, 2, 2, 2, 2, 2, 3, 3]);
+
+        // the paths of the queries leave the trie at nodes with one child
+        assert_eq!(trie.find_key(1), Some(&0b0000_0011));
+        assert_eq!(trie.successor_key(0b0000_0011), Some(&0b1000_0000));
+        assert_eq!(trie.find_key(0b0111_1111), Some(&0b1000_0000));
+        assert_eq!(trie.predecessor_key(0b0111_1111), Some(&0b0000_0011));
+        assert_eq!(trie.predecessor_key(0b1111_1111), Some(&0b1000_0000));
+
+        // the jump pointers to a removed leaf move to its neighbour
+        assert!(trie.remove(&0b0000_0011));
+        assert_eq!(sizes(&trie), vec![1, 2, 2, 2, 2, 2, 2, 2, 2]);
+        assert_eq!(trie.successor_key(0), Some(&0b1000_0000));
+        assert_eq!(trie.predecessor_key(0b0111_1111), Some(&0));
+
+        assert!(trie.remove(&0));
+        assert!(trie.remove(&0b1000_0000));
+        assert_eq!(sizes(&trie), vec![1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(trie.free.len(), trie.nodes.len() - 1);
+        assert_eq!(trie.find_key(0), None);
+    }
+
+    #[test]
+    pub fn borrowed() {
+        let mut trie = XFastTrie::new();
+        for item in [0u8, 7, 8, 100, 200, 255].iter() {
+            trie.add(*item);
+        }
+        for x in 0..=255u8 {
+            assert_eq!(trie.find(&x), trie.find_key(x));
+            assert_eq!(trie.successor(&x), trie.successor_key(x));
+            assert_eq!(trie.predecessor(&x), trie.predecessor_key(x));
+        }
+    }
+}
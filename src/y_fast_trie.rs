@@ -0,0 +1,383 @@
+use crate::binary_trie::{locate, Word};
+use crate::chained_hash_table::ChainedHashTable;
+use crate::random::Random;
+use crate::red_black_tree::RedBlackTree;
+use crate::x_fast_trie::XFastTrie;
+use crate::{SSet, USet};
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+
+// the bucket of the items greater than every representative
+const TAIL: usize = 0;
+
+// the bucket ending at a representative
+#[derive(Debug)]
+struct Representative {
+    key: u64,
+    bucket: usize,
+}
+
+impl PartialEq for Representative {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Representative {}
+
+impl Hash for Representative {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+impl Borrow<u64> for Representative {
+    fn borrow(&self) -> &u64 {
+        &self.key
+    }
+}
+
+/// Each item becomes a representative with probability 1/W. The representatives are kept in an
+/// `XFastTrie`, and the items between two of them in a red-black tree bucket of expected size W,
+/// so `add`, `remove_key`, `find_key`, `successor_key` and `predecessor_key` take O(log W)
+/// expected time in O(n) space.
+///
+/// A bucket is split or merged item by item, O(W log W) with probability O(1/W). The `SSet`
+/// queries first locate their `Q` among the words in O(W) comparisons, as in `XFastTrie`.
+#[derive(Debug)]
+pub struct YFastTrie<T> {
+    representatives: XFastTrie<T>,
+    // the bucket of each representative
+    indices: ChainedHashTable<Representative>,
+    // the items greater than the previous representative and up to the next one
+    buckets: Vec<RedBlackTree<T>>,
+    // the unused slots of `buckets`
+    free: Vec<usize>,
+    size: usize,
+    random: Random,
+}
+
+impl<T: Word> YFastTrie<T> {
+    pub fn new() -> YFastTrie<T> {
+        Self::with_random(Random::new())
+    }
+
+    pub fn with_seed(seed: u64) -> YFastTrie<T> {
+        Self::with_random(Random::with_seed(seed))
+    }
+
+    fn with_random(random: Random) -> YFastTrie<T> {
+        YFastTrie {
+            representatives: XFastTrie::new(),
+            indices: ChainedHashTable::new(),
+            buckets: vec![RedBlackTree::new()],
+            free: vec![],
+            size: 0,
+            random,
+        }
+    }
+
+    fn index(&self, representative: &T) -> usize {
+        match self.indices.find(&representative.to_u64()) {
+            Some(representative) => representative.bucket,
+            None => unreachable!(),
+        }
+    }
+
+    // the bucket `item` belongs in
+    fn bucket(&self, item: T) -> usize {
+        match self.representatives.find_key(item) {
+            Some(representative) => self.index(representative),
+            None => TAIL,
+        }
+    }
+
+    pub fn remove_key(&mut self, item: T) -> bool {
+        self.take_item(item).is_some()
+    }
+
+    /// The least item greater than or equal to `item`.
+    pub fn find_key(&self, item: T) -> Option<&T> {
+        self.buckets[self.bucket(item)].find(&item)
+    }
+
+    /// The least item greater than `item`.
+    pub fn successor_key(&self, item: T) -> Option<&T> {
+        let index = self.bucket(item);
+        if let Some(successor) = self.buckets[index].successor(&item) {
+            return Some(successor);
+        }
+        if index == TAIL {
+            return None;
+        }
+
+        // `item` is the representative ending its bucket
+        match self.representatives.successor_key(item) {
+            Some(representative) => self.buckets[self.index(representative)].first(),
+            None => self.buckets[TAIL].first(),
+        }
+    }
+
+    /// The greatest item less than `item`.
+    pub fn predecessor_key(&self, item: T) -> Option<&T> {
+        self.buckets[self.bucket(item)]
+            .predecessor(&item)
+            .or_else(|| self.representatives.predecessor_key(item))
+    }
+
+    fn take_item(&mut self, item: T) -> Option<T> {
+        let index = self.bucket(item);
+        let item = self.buckets[index].take(&item)?;
+        self.size -= 1;
+
+        if self.indices.remove(&item.to_u64()) {
+            // the rest of the bucket joins the next one
+            self.representatives.take::<T>(&item);
+            let mut bucket = std::mem::take(&mut self.buckets[index]);
+            self.free.push(index);
+
+            let next = self.bucket(item);
+            while let Some(x) = bucket.pop_first() {
+                self.buckets[next].add(x);
+            }
+        }
+        Some(item)
+    }
+
+    fn allocate(&mut self, bucket: RedBlackTree<T>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.buckets[index] = bucket;
+                index
+            }
+            None => {
+                self.buckets.push(bucket);
+                self.buckets.len() - 1
+            }
+        }
+    }
+}
+
+impl<T: Word> Default for YFastTrie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Word> SSet<T> for YFastTrie<T> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, item: T) -> bool {
+        let index = self.bucket(item);
+        if !self.buckets[index].add(item) {
+            return false;
+        }
+        self.size += 1;
+
+        if self.random.next_below(T::W as usize) == 0 {
+            // the new representative takes the items up to it
+            let mut bucket = RedBlackTree::new();
+            while let Some(&first) = self.buckets[index].first() {
+                if first > item {
+                    break;
+                }
+                self.buckets[index].pop_first();
+                bucket.add(first);
+            }
+
+            let bucket = self.allocate(bucket);
+            self.representatives.add(item);
+            self.indices.add(Representative {
+                key: item.to_u64(),
+                bucket,
+            });
+        }
+        true
+    }
+
+    /// O(W) to locate `item`, against O(log W) for `remove_key`.
+    fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<T, Q>(item) {
+            Some((key, true)) => self.take_item(T::from_u64(key)),
+            _ => None,
+        }
+    }
+
+    /// O(W) to locate `item`, against O(log W) for `find_key`.
+    fn find<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (key, _) = locate::<T, Q>(item)?;
+        self.find_key(T::from_u64(key))
+    }
+
+    /// O(W) to locate `item`, against O(log W) for `successor_key`.
+    fn successor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<T, Q>(item)? {
+            (key, true) => self.successor_key(T::from_u64(key)),
+            (key, false) => self.find_key(T::from_u64(key)),
+        }
+    }
+
+    /// O(W) to locate `item`, against O(log W) for `predecessor_key`.
+    fn predecessor<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match locate::<T, Q>(item) {
+            Some((key, _)) => self.predecessor_key(T::from_u64(key)),
+            None => self.last(),
+        }
+    }
+
+    fn first(&self) -> Option<&T> {
+        match self.representatives.first() {
+            Some(representative) => self.buckets[self.index(representative)].first(),
+            None => self.buckets[TAIL].first(),
+        }
+    }
+
+    fn last(&self) -> Option<&T> {
+        self.buckets[TAIL]
+            .last()
+            .or_else(|| self.representatives.last())
+    }
+
+    fn pop_first(&mut self) -> Option<T> {
+        let first = *self.first()?;
+        self.take_item(first)
+    }
+
+    fn pop_last(&mut self) -> Option<T> {
+        let last = *self.last()?;
+        self.take_item(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{YFastTrie, TAIL};
+    use crate::{SSet, USet};
+
+    // every representative ends its bucket, after the items of the previous one
+    fn check(trie: &YFastTrie<u64>) {
+        let mut count = 0;
+        let mut previous = None;
+        let mut representative = trie.representatives.first().copied();
+        loop {
+            let bucket = match representative {
+                Some(representative) => &trie.buckets[trie.index(&representative)],
+                None => &trie.buckets[TAIL],
+            };
+            if representative.is_some() {
+                assert_eq!(bucket.last(), representative.as_ref());
+            }
+            if let (Some(previous), Some(first)) = (previous, bucket.first()) {
+                assert!(*first > previous);
+            }
+            count += bucket.size();
+
+            previous = representative;
+            representative = match representative {
+                Some(representative) => trie.representatives.successor_key(representative).copied(),
+                None => break,
+            };
+        }
+        assert_eq!(count, trie.size());
+        assert_eq!(trie.indices.size(), trie.representatives.size());
+        assert_eq!(
+            trie.buckets.len() - trie.free.len(),
+            trie.representatives.size() + 1
+        );
+    }
+
+    #[test]
+    pub fn sset() {
+        crate::tests::sset(YFastTrie::with_seed(1));
+    }
+
+    #[test]
+    pub fn buckets() {
+        let mut trie = YFastTrie::with_seed(7);
+        // splits the bucket of each new representative
+        for item in (0..4096u64).map(|x| x * 3) {
+            assert!(trie.add(item));
+            if item % 768 == 0 {
+                check(&trie);
+            }
+        }
+        assert_eq!(trie.size(), 4096);
+        assert!(trie.representatives.size() > 0);
+        check(&trie);
+
+        for x in 0..4096 * 3 - 3 {
+            let successor = (x / 3 + 1) * 3;
+            assert_eq!(trie.successor_key(x), Some(&successor));
+            assert_eq!(trie.find_key(x), Some(&(x.div_ceil(3) * 3)));
+        }
+
+        // merges the buckets of the removed representatives
+        let representatives = trie.representatives.size();
+        for item in (0..4096u64).map(|x| x * 3).filter(|x| x % 2 == 0) {
+            assert!(trie.remove_key(item));
+            if item % 768 == 0 {
+                check(&trie);
+            }
+        }
+        assert_eq!(trie.size(), 2048);
+        assert!(trie.representatives.size() < representatives);
+        assert_eq!(
+            trie.free.len(),
+            representatives - trie.representatives.size()
+        );
+        check(&trie);
+        assert_eq!(trie.first(), Some(&3));
+        assert_eq!(trie.last(), Some(&(4095 * 3)));
+        assert_eq!(trie.predecessor_key(3), None);
+        assert_eq!(trie.predecessor_key(10), Some(&9));
+
+        assert_eq!(trie.pop_first(), Some(3));
+        assert_eq!(trie.pop_last(), Some(4095 * 3));
+        check(&trie);
+
+        // the new buckets take the freed slots
+        let slots = trie.buckets.len();
+        for item in (0..4096u64).map(|x| x * 3).filter(|x| x % 2 == 0) {
+            assert!(trie.add(item));
+        }
+        assert_eq!(trie.buckets.len(), slots);
+        check(&trie);
+    }
+
+    #[test]
+    pub fn borrowed() {
+        let mut trie = YFastTrie::with_seed(3);
+        for item in (0..=255u8).filter(|x| x % 5 == 0) {
+            trie.add(item);
+        }
+        for x in 0..=255u8 {
+            assert_eq!(trie.find(&x), trie.find_key(x));
+            assert_eq!(trie.successor(&x), trie.successor_key(x));
+            assert_eq!(trie.predecessor(&x), trie.predecessor_key(x));
+        }
+        assert_eq!(trie.take(&10), Some(10));
+        assert_eq!(trie.take(&11), None);
+        assert!(!trie.remove_key(10));
+        assert!(trie.remove(&15));
+        assert!(!trie.remove(&15));
+        assert_eq!(trie.size(), 50);
+    }
+}